@@ -0,0 +1,39 @@
+use isd4hqc::hqc::params::HqcPkeParams;
+use isd4hqc::hqc::pke;
+use isd4hqc::hqc::types::EkPke;
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::instance::QcDoubleCirculant;
+use isd4hqc::isd::Stern;
+
+/// HQC-shaped toy parameters: [3, 1] RS code over RM(1, 7) x3 and W = 2.
+#[derive(Clone, Copy, Debug)]
+struct ToyParams;
+impl HqcPkeParams for ToyParams {
+    const N1: usize = 3;
+    const N2: usize = 384;
+    const N: usize = 1153;
+    const K: usize = 8;
+    const W: usize = 2;
+    const W_R: usize = 2;
+    const W_E: usize = 2;
+    const RM_MULT: usize = 3;
+    const RS_GEN_POLY: &'static [u8] = &[1, 6, 8];
+}
+
+fn main() {
+    let (ek, _dk) = pke::keygen::<ToyParams>([0u8; 32]);
+    let c = pke::encrypt::<ToyParams>(&ek, &[0x42], [1u8; 32]).unwrap();
+
+    // Only public bytes from here on.
+    let ek = EkPke::<ToyParams>::from_bytes(&ek.to_bytes()).unwrap();
+    let instance = QcDoubleCirculant::from(&ek);
+    let attack = Stern::new(1, 12, Some(200000), Some(0));
+    match attack.solve(instance.h.n, instance.w, &instance.h, &instance.s) {
+        Ok(Some(y)) => match pke::decrypt_with_secret_vector::<ToyParams>(&y, &c) {
+            Some(m) => println!("Decrypted with recovered y: {:02x?}", m),
+            None => println!("Recovered y does not decrypt."),
+        },
+        Ok(None) => println!("No solution found."),
+        Err(e) => println!("Error during attack: {}", e),
+    }
+}
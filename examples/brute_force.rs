@@ -16,7 +16,7 @@ fn main() {
         Ok(Some(solution_y)) => {
             println!("Solution found y: {:?}", solution_y);
             println!("Original y: {:?}", y);
-            println!("Match y: {}", &solution_y == y);
+            println!("Verdict: {:?}", instance.classify(&solution_y));
             let hy = solution_y.mul_bitpacked(&h);
             let mut solution_x = s.clone();
             solution_x.xor_in_place(&hy);
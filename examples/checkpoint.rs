@@ -0,0 +1,39 @@
+use isd4hqc::isd::Stern;
+use isd4hqc::isd::budget::AttackBudget;
+use isd4hqc::isd::checkpoint::{Checkpoint, Resumable};
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::hqc::types::Seed32;
+use std::time::Duration;
+fn main() {
+    let seed_pke: Seed32 = [0u8; 32];
+    let params = HqcExperimentParams::sparse_parameters_hqc_3(4);
+    let instance = params.keygen(seed_pke).unwrap();
+    let (h, s) = instance.get_public_key();
+    let attack = Stern::new(1, 12, None, None);
+    let path = std::env::temp_dir().join("isd4hqc-stern-example.ckpt");
+    // Continue an earlier run if it left a checkpoint behind.
+    let mut checkpoint = Checkpoint::load(&path).unwrap_or_default();
+    loop {
+        let budget = AttackBudget::unlimited().with_time_limit(Duration::from_millis(20));
+        match attack.resume(params.n, params.w, h, s, &budget, &mut checkpoint) {
+            Ok(Some(solution_y)) => {
+                println!("Solution found after {} iterations", checkpoint.stats.iterations);
+                println!("Verdict: {:?}", instance.classify(&solution_y));
+                let _ = std::fs::remove_file(&path);
+                break;
+            }
+            Ok(None) if checkpoint.stats.stopped.is_some() => {
+                checkpoint.save(&path).expect("save checkpoint");
+                println!("Checkpoint at iteration {}", checkpoint.stats.iterations);
+            }
+            Ok(None) => {
+                println!("No solution found.");
+                break;
+            }
+            Err(e) => {
+                println!("Error during attack: {}", e);
+                break;
+            }
+        }
+    }
+}
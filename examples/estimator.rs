@@ -0,0 +1,28 @@
+use isd4hqc::isd::estimator::{self, IsdAlgorithm};
+use isd4hqc::isd::params::HqcExperimentParams;
+fn main() {
+    let levels = [
+        ("hqc-1", HqcExperimentParams::hqc_1()),
+        ("hqc-3", HqcExperimentParams::hqc_3()),
+        ("hqc-5", HqcExperimentParams::hqc_5()),
+    ];
+    for (name, params) in levels {
+        println!("{} (n = {}, w = {})", name, params.n, params.w);
+        for est in estimator::estimate_all(&params) {
+            let p = est.params;
+            let mut line = format!(
+                "  {:<7} time 2^{:.2}  memory 2^{:.2}",
+                est.algorithm.name(),
+                est.time_log2,
+                est.memory_log2
+            );
+            if est.algorithm != IsdAlgorithm::Prange {
+                line += &format!("  p = {}, l = {}", p.p, p.l);
+            }
+            if est.algorithm == IsdAlgorithm::Bjmm {
+                line += &format!(", depth = {}, eps = {:?}", p.depth, p.eps);
+            }
+            println!("{}", line);
+        }
+    }
+}
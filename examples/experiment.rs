@@ -0,0 +1,23 @@
+use isd4hqc::isd::experiment::{Experiment, ExperimentGrid, RowFormat};
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::{Dumer, Prange, Stern};
+use std::time::Duration;
+fn main() {
+    // `cargo run --example experiment -- csv` for CSV, JSON lines otherwise.
+    let format = match std::env::args().nth(1).as_deref() {
+        Some("csv") => RowFormat::Csv,
+        _ => RowFormat::Jsonl,
+    };
+    let grid = ExperimentGrid::new(3, 0)
+        .with_family(HqcExperimentParams::sparse_parameters_hqc_3, 2..=4)
+        .with_point(150, 3);
+    let experiment = Experiment::new(grid)
+        .with_attack("prange", |seed| Prange::new(Some(200000), Some(seed)))
+        .with_attack("stern-p1-l12", |seed| Stern::new(1, 12, Some(200000), Some(seed)))
+        .with_attack("dumer-p1-l12", |seed| Dumer::new(1, 12, Some(200000), Some(seed)))
+        .with_time_limit(Duration::from_secs(30));
+    let mut out = std::io::stdout().lock();
+    if let Err(e) = experiment.run_to(&mut out, format) {
+        eprintln!("Experiment failed: {}", e);
+    }
+}
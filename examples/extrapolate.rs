@@ -0,0 +1,43 @@
+use isd4hqc::isd::estimator::{IsdAlgorithm, SdCostModel};
+use isd4hqc::isd::experiment::{Experiment, ExperimentGrid};
+use isd4hqc::isd::fit::{Fit, FitModel, WorkMeasure, mean_samples};
+use isd4hqc::isd::params::HqcFamily;
+use isd4hqc::isd::Prange;
+fn main() {
+    let family = HqcFamily::Hqc1;
+    let grid = ExperimentGrid::new(4, 0).with_family(|w| HqcFamily::Hqc1.params(w), 1..=4);
+    let experiment = Experiment::new(grid)
+        .with_attack("prange", |seed| Prange::new(Some(1_000_000), Some(seed)));
+    let mut rows = Vec::new();
+    if let Err(e) = experiment.run(|row| {
+        rows.push(row);
+        Ok(())
+    }) {
+        println!("Experiment failed: {}", e);
+        return;
+    }
+    let data = mean_samples(&rows, "prange", family, WorkMeasure::Iterations);
+    for s in &data {
+        let predicted = SdCostModel::hqc(family.params(s.w).n, s.w).estimate(IsdAlgorithm::Prange);
+        println!(
+            "w = {}: measured log2(iterations) = {:.2}, predicted {:.2}",
+            s.w, s.log2_work, predicted.iterations_log2
+        );
+    }
+    match Fit::new(&data, FitModel::Linear) {
+        Ok(fit) => {
+            let e = fit.extrapolate_family(family, 0.95);
+            println!("Slope: {:.3} bits per unit of w (R² = {:.3})", fit.slope(), fit.r_squared);
+            println!(
+                "{} (n = {}, w = {}): log2(iterations) = {:.1}, 95% CI [{:.1}, {:.1}]",
+                family.name(),
+                e.params.n,
+                e.params.w,
+                e.log2_work,
+                e.lower,
+                e.upper
+            );
+        }
+        Err(e) => println!("Fit failed: {}", e),
+    }
+}
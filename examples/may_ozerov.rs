@@ -0,0 +1,30 @@
+use isd4hqc::hqc::types::Seed32;
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::{Dumer, MayOzerov, NnBackend};
+fn main() {
+    let seed_pke: Seed32 = [0u8; 32];
+    let params = HqcExperimentParams::sparse_parameters_hqc_3(3);
+    let instance = params.keygen(seed_pke).unwrap();
+    let (h, s) = instance.get_public_key();
+    let attacks: Vec<(&str, Box<dyn Attack>)> = vec![
+        ("dumer p=1 l=12", Box::new(Dumer::new(1, 12, Some(200000), Some(1)))),
+        ("may-ozerov exhaustive", Box::new(MayOzerov::new(1, 0, NnBackend::Exhaustive, Some(200000), Some(1)))),
+        (
+            "may-ozerov bit sampling",
+            Box::new(MayOzerov::new(1, 0, NnBackend::BitSampling { bits: 24, repetitions: 4 }, Some(200000), Some(1))),
+        ),
+    ];
+    for (label, attack) in attacks {
+        match attack.solve_with_report(params.n, params.w, h, s) {
+            Ok(report) => {
+                let verdict = report.solution.as_ref().map(|y| instance.classify(y));
+                println!(
+                    "{}: {:?}, {} iterations, {} candidate checks, {:?}",
+                    label, verdict, report.stats.iterations, report.stats.candidate_checks, report.wall_time
+                );
+            }
+            Err(e) => println!("{}: error during attack: {}", label, e),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use isd4hqc::hqc::params::HqcPkeParams;
+use isd4hqc::hqc::pke;
+use isd4hqc::isd::attack::AttackStats;
+use isd4hqc::isd::budget::AttackBudget;
+use isd4hqc::isd::message_recovery::HqcMessageRecoveryInstance;
+use isd4hqc::isd::Stern;
+
+/// HQC-shaped toy parameters: [3, 1] RS code over RM(1, 7) x3 and W_R = 2.
+#[derive(Clone, Copy, Debug)]
+struct ToyParams;
+impl HqcPkeParams for ToyParams {
+    const N1: usize = 3;
+    const N2: usize = 384;
+    const N: usize = 1153;
+    const K: usize = 8;
+    const W: usize = 2;
+    const W_R: usize = 2;
+    const W_E: usize = 2;
+    const RM_MULT: usize = 3;
+    const RS_GEN_POLY: &'static [u8] = &[1, 6, 8];
+}
+
+fn main() {
+    let (ek, _dk) = pke::keygen::<ToyParams>([0u8; 32]);
+    let m = [0x42u8];
+    let c = pke::encrypt::<ToyParams>(&ek, &m, [1u8; 32]).unwrap();
+    let instance = HqcMessageRecoveryInstance::new(&ek, &c);
+    let attack = Stern::new(1, 12, Some(200000), Some(0));
+    let mut stats = AttackStats::default();
+    match instance.solve_within(&attack, &AttackBudget::unlimited(), &mut stats) {
+        Ok(Some(recovered)) => {
+            println!("Recovered message: {:02x?}", recovered);
+            println!("Match m: {}", recovered == m);
+        }
+        Ok(None) => println!("No message recovered."),
+        Err(e) => println!("Error during attack: {}", e),
+    }
+    println!("Iterations: {}", stats.iterations);
+}
@@ -0,0 +1,37 @@
+use isd4hqc::isd::Stern;
+use isd4hqc::isd::budget::AttackBudget;
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::runner::ParallelRunner;
+use isd4hqc::hqc::types::Seed32;
+fn main() {
+    let seed_pke: Seed32 = [0u8; 32];
+    let params = HqcExperimentParams::sparse_parameters_hqc_3(4);
+    let instance = params.keygen(seed_pke).unwrap();
+    let threads = std::thread::available_parallelism().map_or(4, |t| t.get());
+    let runner = ParallelRunner::new(threads, 42);
+    println!("Running Stern on {} threads", threads);
+    let (h, s) = instance.get_public_key();
+    match runner.run(
+        |seed| Stern::new(1, 12, Some(200000), Some(seed)),
+        params.n,
+        params.w,
+        h,
+        s,
+        &AttackBudget::unlimited(),
+    ) {
+        Ok(report) => {
+            let stats = report.total_stats();
+            println!("Winner: {:?} (seed {:?})", report.winner, stats.seed);
+            println!("Iterations over all workers: {}", stats.iterations);
+            println!("Wall time: {:?}", report.wall_time);
+            if let Some(solution_y) = report.solution {
+                println!("Verdict: {:?}", instance.classify(&solution_y));
+            } else {
+                println!("No solution found.");
+            }
+        }
+        Err(e) => {
+            println!("Error during attack: {}", e);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use isd4hqc::hqc::params::{Hqc1Params, Hqc3Params, Hqc5Params, HqcPkeParams};
+use isd4hqc::isd::estimator::quantum::{self, QuantumCostEstimate, NIST_MAXDEPTHS_LOG2};
+fn print_level(name: &str, estimates: &[QuantumCostEstimate]) {
+    println!("{}", name);
+    for est in estimates {
+        println!(
+            "  {:<14} MAXDEPTH 2^{:<3} gates 2^{:.2} (unbounded 2^{:.2}, depth 2^{:.2})  qubits 2^{:.2}  p = {}, l = {}  category {}",
+            est.algorithm.name(),
+            est.maxdepth_log2,
+            est.maxdepth_gates_log2,
+            est.gates_log2,
+            est.depth_log2,
+            est.qubits_log2,
+            est.params.p,
+            est.params.l,
+            est.nist_category()
+        );
+    }
+}
+fn level<P: HqcPkeParams>(name: &str) {
+    for &d in NIST_MAXDEPTHS_LOG2.iter() {
+        print_level(
+            &format!("{} key recovery (N = {}, W = {})", name, P::N, P::W),
+            &quantum::estimate_key_recovery::<P>(d),
+        );
+        print_level(
+            &format!("{} message recovery (N = {}, W_R = {})", name, P::N, P::W_R),
+            &quantum::estimate_message_recovery::<P>(d),
+        );
+    }
+}
+fn main() {
+    level::<Hqc1Params>("hqc-1");
+    level::<Hqc3Params>("hqc-3");
+    level::<Hqc5Params>("hqc-5");
+}
@@ -0,0 +1,21 @@
+use isd4hqc::isd::attack::{Attack, AttackStats};
+use isd4hqc::isd::budget::AttackBudget;
+use isd4hqc::isd::instance::{RandomCode, SdAttack, SdInstance};
+use isd4hqc::isd::{Prange, Stern};
+fn main() {
+    // Random [400, 200] code with a planted error of weight 8: the baseline for HQC-shaped runs.
+    let (code, planted) = RandomCode::random(400, 200, 8, 0).unwrap();
+    let budget = AttackBudget::unlimited();
+    let prange = Prange::new(Some(2000000), Some(1));
+    let stern = Stern::new(2, 12, Some(200000), Some(1));
+    for (name, result) in [
+        (prange.name(), prange.solve_instance(&code, &budget, &mut AttackStats::default())),
+        (stern.name(), stern.solve_instance(&code, &budget, &mut AttackStats::default())),
+    ] {
+        match result {
+            Ok(Some(e)) => println!("{}: found e, solution {}, planted {}", name, code.is_solution(&e), e == planted),
+            Ok(None) => println!("{}: no solution found", name),
+            Err(e) => println!("{}: error {}", name, e),
+        }
+    }
+}
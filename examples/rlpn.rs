@@ -0,0 +1,23 @@
+use isd4hqc::hqc::types::Seed32;
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::{Rlpn, params::HqcExperimentParams};
+fn main() {
+    let seed_pke: Seed32 = [0u8; 32];
+    let params = HqcExperimentParams::new(150, 3);
+    let instance = params.keygen(seed_pke).unwrap();
+    let (h, s) = instance.get_public_key();
+    for checks in [1000, 4000] {
+        let attack = Rlpn::new(10, 2, 58, checks, Some(20), Some(1));
+        println!("Using attack: {} with {} checks per trial", attack.name(), checks);
+        match attack.solve_with_report(params.n, params.w, h, s) {
+            Ok(r) => println!(
+                "Verdict: {:?}, {} trials, {} eliminations, {:?}",
+                r.solution.as_ref().map(|y| instance.classify(y)),
+                r.stats.iterations,
+                r.stats.eliminations,
+                r.wall_time
+            ),
+            Err(e) => println!("Error during attack: {}", e),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use isd4hqc::hqc::types::Seed32;
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::budget::AttackBudget;
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::{Bjmm, Sieve};
+fn main() {
+    let seed_pke: Seed32 = [0u8; 32];
+    for w in 2..=3 {
+        let params = HqcExperimentParams::sparse_parameters_hqc_5(w);
+        let instance = params.keygen(seed_pke).unwrap();
+        let (h, s) = instance.get_public_key();
+        println!("n = {}, w = {}", params.n, params.w);
+
+        let bjmm = Bjmm::new(2, 2, 14, vec![1], Some(200000), Some(1));
+        match bjmm.solve_with_report(params.n, params.w, h, s) {
+            Ok(r) => println!(
+                "  BJMM: {:?}, {} iterations, max list {}, {:?}",
+                r.solution.as_ref().map(|y| instance.classify(y)),
+                r.stats.iterations,
+                r.stats.max_table_len,
+                r.wall_time
+            ),
+            Err(e) => println!("  BJMM: error during attack: {}", e),
+        }
+
+        for memory in [1 << 20, 1 << 22] {
+            let sieve = Sieve::new(2, 14, 2, memory, Some(200000), Some(1));
+            let budget = AttackBudget::unlimited();
+            match sieve.report_levels_within(params.n, params.w, h, s, &budget) {
+                Ok((r, levels)) => {
+                    let means: Vec<String> = levels.iter().map(|l| format!("{:.0}", l.mean())).collect();
+                    println!(
+                        "  Sieve ({} KiB, lists <= {}): {:?}, {} iterations, mean list sizes [{}], {:?}",
+                        memory >> 10,
+                        sieve.list_len(),
+                        r.solution.as_ref().map(|y| instance.classify(y)),
+                        r.stats.iterations,
+                        means.join(", "),
+                        r.wall_time
+                    );
+                }
+                Err(e) => println!("  Sieve: error during attack: {}", e),
+            }
+        }
+    }
+}
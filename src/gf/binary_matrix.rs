@@ -0,0 +1,433 @@
+//! Dense matrices over GF(2).
+//!
+//! Rows are stored back to back in one `Vec<u64>`, LSB-first inside each word like `HqcGf2`.
+//! Elimination uses the Method of Four Russians (M4RI): pivots are found `M4RI_K` columns at
+//! a time, and every other row is cleared on the whole block with one lookup into a table of
+//! all `2^k` combinations of the block's pivot rows.
+
+use crate::gf::gf2::Gf2Construct;
+use crate::hqc::hqcgf2::HqcGf2;
+
+/// Columns per M4RI block (the lookup table has `2^M4RI_K` rows).
+pub const M4RI_K: usize = 8;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BinaryMatrix {
+    rows: usize,
+    cols: usize,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BinaryMatrix {
+    /// All-zero `rows x cols` matrix.
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        let stride = HqcGf2::word_len(cols);
+        Self {
+            rows,
+            cols,
+            stride,
+            words: vec![0u64; rows * stride],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zero(n, n);
+        for i in 0..n {
+            m.set(i, i);
+        }
+        m
+    }
+
+    /// Build from rows of equal length.
+    pub fn from_rows(rows: &[HqcGf2]) -> Self {
+        let cols = rows.first().map_or(0, |r| r.n);
+        let mut m = Self::zero(rows.len(), cols);
+        for (r, row) in rows.iter().enumerate() {
+            m.set_row(r, row);
+        }
+        m
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Words per row.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    #[inline]
+    pub fn row(&self, r: usize) -> &[u64] {
+        &self.words[r * self.stride..(r + 1) * self.stride]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, r: usize) -> &mut [u64] {
+        &mut self.words[r * self.stride..(r + 1) * self.stride]
+    }
+
+    #[inline]
+    pub fn get(&self, r: usize, c: usize) -> bool {
+        debug_assert!(r < self.rows && c < self.cols);
+        (self.words[r * self.stride + (c >> 6)] >> (c & 63)) & 1 == 1
+    }
+
+    #[inline]
+    pub fn set(&mut self, r: usize, c: usize) {
+        debug_assert!(r < self.rows && c < self.cols);
+        self.words[r * self.stride + (c >> 6)] |= 1u64 << (c & 63);
+    }
+
+    #[inline]
+    pub fn clear(&mut self, r: usize, c: usize) {
+        debug_assert!(r < self.rows && c < self.cols);
+        self.words[r * self.stride + (c >> 6)] &= !(1u64 << (c & 63));
+    }
+
+    #[inline]
+    pub fn toggle(&mut self, r: usize, c: usize) {
+        debug_assert!(r < self.rows && c < self.cols);
+        self.words[r * self.stride + (c >> 6)] ^= 1u64 << (c & 63);
+    }
+
+    #[inline]
+    pub fn set_to(&mut self, r: usize, c: usize, val: bool) {
+        if val { self.set(r, c) } else { self.clear(r, c) }
+    }
+
+    /// Clear all bits to zero.
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Overwrite row r with `v` (`v.n <= cols`, the remaining bits are cleared).
+    pub fn set_row(&mut self, r: usize, v: &HqcGf2) {
+        assert!(v.n <= self.cols, "row longer than matrix width");
+        let row = self.row_mut(r);
+        row.fill(0);
+        row[..v.words.len()].copy_from_slice(&v.words);
+    }
+
+    /// Row r as a vector of length `cols`.
+    pub fn row_vec(&self, r: usize) -> HqcGf2 {
+        self.row_bits(r, 0, self.cols)
+    }
+
+    /// Bits `start..start + len` of row r as a vector of length `len`.
+    pub fn row_bits(&self, r: usize, start: usize, len: usize) -> HqcGf2 {
+        assert!(start + len <= self.cols, "bit range exceeds matrix width");
+        let mut out = HqcGf2::zero_with_len(len);
+        let row = self.row(r);
+        let (ws, bs) = (start >> 6, start & 63);
+        for (i, w) in out.words.iter_mut().enumerate() {
+            let lo = row.get(ws + i).copied().unwrap_or(0) >> bs;
+            let hi = if bs == 0 { 0 } else { row.get(ws + i + 1).copied().unwrap_or(0) << (64 - bs) };
+            *w = lo | hi;
+        }
+        out.mask_tail();
+        out
+    }
+
+    /// Up to 64 bits `start..start + len` of row r, bit i of the result is column `start + i`.
+    #[inline]
+    pub fn row_bits_u64(&self, r: usize, start: usize, len: usize) -> u64 {
+        debug_assert!(len <= 64 && start + len <= self.cols);
+        if len == 0 {
+            return 0;
+        }
+        let row = self.row(r);
+        let (ws, bs) = (start >> 6, start & 63);
+        let mut v = row[ws] >> bs;
+        if bs != 0 && ws + 1 < self.stride {
+            v |= row[ws + 1] << (64 - bs);
+        }
+        if len == 64 { v } else { v & ((1u64 << len) - 1) }
+    }
+
+    /// Column c as a vector of length `rows`.
+    pub fn column(&self, c: usize) -> HqcGf2 {
+        let mut out = HqcGf2::zero_with_len(self.rows);
+        for r in 0..self.rows {
+            if self.get(r, c) {
+                out.set(r);
+            }
+        }
+        out
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        let (lo, hi) = (a.min(b), a.max(b));
+        let (head, tail) = self.words.split_at_mut(hi * self.stride);
+        head[lo * self.stride..(lo + 1) * self.stride].swap_with_slice(&mut tail[..self.stride]);
+    }
+
+    pub fn swap_cols(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        for r in 0..self.rows {
+            let (x, y) = (self.get(r, a), self.get(r, b));
+            if x != y {
+                self.toggle(r, a);
+                self.toggle(r, b);
+            }
+        }
+    }
+
+    /// Row `dst` ^= row `src`.
+    #[inline]
+    pub fn xor_rows(&mut self, dst: usize, src: usize) {
+        self.xor_rows_from(dst, src, 0);
+    }
+
+    /// Row `dst` ^= row `src`, only on words `first_word..`.
+    #[inline]
+    fn xor_rows_from(&mut self, dst: usize, src: usize, first_word: usize) {
+        debug_assert_ne!(dst, src);
+        let stride = self.stride;
+        let (d, s) = if dst < src {
+            let (head, tail) = self.words.split_at_mut(src * stride);
+            (&mut head[dst * stride..(dst + 1) * stride], &tail[..stride])
+        } else {
+            let (head, tail) = self.words.split_at_mut(dst * stride);
+            (&mut tail[..stride], &head[src * stride..(src + 1) * stride])
+        };
+        for (a, b) in d[first_word..].iter_mut().zip(&s[first_word..]) {
+            *a ^= *b;
+        }
+    }
+
+    /// `A·x` for a vector x of length `cols`.
+    pub fn mul_vec(&self, x: &HqcGf2) -> HqcGf2 {
+        assert_eq!(x.n, self.cols, "length mismatch");
+        let mut out = HqcGf2::zero_with_len(self.rows);
+        for r in 0..self.rows {
+            let parity = self
+                .row(r)
+                .iter()
+                .zip(&x.words)
+                .fold(0u32, |acc, (a, b)| acc ^ (a & b).count_ones());
+            if parity & 1 == 1 {
+                out.set(r);
+            }
+        }
+        out
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut out = Self::zero(self.cols, self.rows);
+        self.transpose_into(&mut out);
+        out
+    }
+
+    /// `out = self^T`, reusing `out`'s storage. Works on 64 x 64 blocks.
+    pub fn transpose_into(&self, out: &mut Self) {
+        assert!(out.rows == self.cols && out.cols == self.rows, "transpose shape mismatch");
+        out.words.fill(0);
+        let mut block = [0u64; 64];
+        for rb in (0..self.rows).step_by(64) {
+            let rows_in_block = (self.rows - rb).min(64);
+            for cw in 0..self.stride {
+                for (i, b) in block.iter_mut().enumerate() {
+                    *b = if i < rows_in_block { self.words[(rb + i) * self.stride + cw] } else { 0 };
+                }
+                transpose64(&mut block);
+                for (j, &b) in block.iter().enumerate() {
+                    let oc = (cw << 6) + j;
+                    if oc >= self.cols {
+                        break;
+                    }
+                    out.words[oc * out.stride + (rb >> 6)] = b;
+                }
+            }
+        }
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().echelonize().len()
+    }
+
+    /// Reduced row echelon form over all columns. Returns the pivot column of each of the
+    /// first `rank` rows.
+    pub fn echelonize(&mut self) -> Vec<usize> {
+        let cols = self.cols;
+        self.m4ri(cols, false).expect("non-strict elimination cannot fail")
+    }
+
+    /// Reduce the first `pivots` columns to identity on rows `0..pivots`; the remaining rows
+    /// end up zero on those columns (the l-row-deferred form used by ISD with a window).
+    /// false if those columns are linearly dependent.
+    pub fn systematize(&mut self, pivots: usize) -> bool {
+        assert!(pivots <= self.rows && pivots <= self.cols, "too many pivots");
+        self.m4ri(pivots, true).is_some()
+    }
+
+    /// Basis of the right kernel `{x : A·x = 0}`, each vector of length `cols`.
+    pub fn kernel(&self) -> Vec<HqcGf2> {
+        let mut m = self.clone();
+        let pivots = m.echelonize();
+        let mut is_pivot = vec![false; self.cols];
+        for &p in &pivots {
+            is_pivot[p] = true;
+        }
+        let mut basis = Vec::new();
+        for f in (0..self.cols).filter(|&c| !is_pivot[c]) {
+            let mut x = HqcGf2::zero_with_len(self.cols);
+            x.set(f);
+            for (i, &p) in pivots.iter().enumerate() {
+                if m.get(i, f) {
+                    x.set(p);
+                }
+            }
+            basis.push(x);
+        }
+        basis
+    }
+
+    /// Some x with `A·x = b`, None if the system is inconsistent.
+    pub fn solve(&self, b: &HqcGf2) -> Option<HqcGf2> {
+        assert_eq!(b.n, self.rows, "length mismatch");
+        let mut aug = Self::zero(self.rows, self.cols + 1);
+        for r in 0..self.rows {
+            let (src, dst) = (r * self.stride, r * aug.stride);
+            aug.words[dst..dst + self.stride].copy_from_slice(&self.words[src..src + self.stride]);
+            aug.set_to(r, self.cols, b.get(r));
+        }
+        let pivots = aug.m4ri(self.cols, false).expect("non-strict elimination cannot fail");
+        if (pivots.len()..self.rows).any(|r| aug.get(r, self.cols)) {
+            return None;
+        }
+        let mut x = HqcGf2::zero_with_len(self.cols);
+        for (i, &p) in pivots.iter().enumerate() {
+            if aug.get(i, self.cols) {
+                x.set(p);
+            }
+        }
+        Some(x)
+    }
+
+    /// M4RI reduced row echelon form on columns `0..col_limit`.
+    /// In strict mode every one of those columns must get a pivot (None otherwise), so the
+    /// pivot of row r is column r.
+    fn m4ri(&mut self, col_limit: usize, strict: bool) -> Option<Vec<usize>> {
+        let mut pivots: Vec<usize> = Vec::new();
+        let mut table = vec![0u64; (1 << M4RI_K) * self.stride];
+        let mut r = 0;
+        let mut c = 0;
+        while c < col_limit && r < self.rows {
+            let kk = M4RI_K.min(col_limit - c);
+            let mut block: Vec<usize> = Vec::with_capacity(kk);
+            for col in c..c + kk {
+                let base = r + block.len();
+                if base >= self.rows {
+                    break;
+                }
+                let mut found = None;
+                for i in base..self.rows {
+                    for (f, &pc) in block.iter().enumerate() {
+                        if self.get(i, pc) {
+                            self.xor_rows(i, r + f);
+                        }
+                    }
+                    if self.get(i, col) {
+                        found = Some(i);
+                        break;
+                    }
+                }
+                let Some(i) = found else {
+                    if strict {
+                        return None;
+                    }
+                    continue;
+                };
+                self.swap_rows(i, base);
+                for f in 0..block.len() {
+                    if self.get(r + f, col) {
+                        self.xor_rows(r + f, base);
+                    }
+                }
+                block.push(col);
+            }
+            if strict && block.len() < kk {
+                return None;
+            }
+            let k = block.len();
+            if k > 0 {
+                self.clear_block(&block, r, &mut table);
+            }
+            pivots.extend_from_slice(&block);
+            r += k;
+            c += kk;
+        }
+        if strict && pivots.len() < col_limit {
+            return None;
+        }
+        Some(pivots)
+    }
+
+    /// Clear the pivot columns `block` (pivot rows `r..r + block.len()`) in every other row
+    /// with one table lookup per row.
+    fn clear_block(&mut self, block: &[usize], r: usize, table: &mut [u64]) {
+        let k = block.len();
+        let stride = self.stride;
+        // Rows at or below r are zero left of the block, so only these words can change.
+        let first_word = block[0] >> 6;
+        for idx in 1usize..(1 << k) {
+            let b = idx.trailing_zeros() as usize;
+            let prev = idx & (idx - 1);
+            for wi in first_word..stride {
+                table[idx * stride + wi] = table[prev * stride + wi] ^ self.words[(r + b) * stride + wi];
+            }
+        }
+        let contiguous = block.windows(2).all(|p| p[1] == p[0] + 1);
+        for i in 0..self.rows {
+            if i >= r && i < r + k {
+                continue;
+            }
+            let idx = if contiguous {
+                self.row_bits_u64(i, block[0], k) as usize
+            } else {
+                block
+                    .iter()
+                    .enumerate()
+                    .fold(0usize, |acc, (f, &pc)| acc | ((self.get(i, pc) as usize) << f))
+            };
+            if idx != 0 {
+                let row = &mut self.words[i * stride..(i + 1) * stride];
+                let t = &table[idx * stride..(idx + 1) * stride];
+                for wi in first_word..stride {
+                    row[wi] ^= t[wi];
+                }
+            }
+        }
+    }
+}
+
+/// In-place transpose of a 64 x 64 bit block: bit j of word i <-> bit i of word j.
+fn transpose64(a: &mut [u64; 64]) {
+    let mut j = 32;
+    let mut m: u64 = 0x0000_0000_FFFF_FFFF;
+    while j != 0 {
+        let mut k = 0;
+        while k < 64 {
+            let t = ((a[k] >> j) ^ a[k + j]) & m;
+            a[k] ^= t << j;
+            a[k + j] ^= t;
+            k = (k + j + 1) & !j;
+        }
+        j >>= 1;
+        m ^= m << j;
+    }
+}
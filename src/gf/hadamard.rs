@@ -0,0 +1,22 @@
+use core::ops::{Add, Sub};
+
+/// In-place Walsh–Hadamard transform (length must be power-of-two).
+///
+/// `v[x]` becomes `sum_z v[z]·(-1)^<x, z>`. The transform is its own inverse up to a factor
+/// `v.len()`. Callers pick T wide enough for `v.len()·max|v[z]|`.
+pub fn hadamard_transform<T: Copy + Add<Output = T> + Sub<Output = T>>(v: &mut [T]) {
+    debug_assert!(v.len().is_power_of_two());
+    let mut len = 1;
+    while len < v.len() {
+        let step = len << 1;
+        for i in (0..v.len()).step_by(step) {
+            for j in 0..len {
+                let a = v[i + j];
+                let b = v[i + j + len];
+                v[i + j] = a + b;
+                v[i + j + len] = a - b;
+            }
+        }
+        len = step;
+    }
+}
@@ -1,3 +1,6 @@
 pub mod gf2;
 pub mod gf256;
-pub use gf2::*;
\ No newline at end of file
+pub mod binary_matrix;
+pub mod hadamard;
+pub use gf2::*;
+pub use binary_matrix::BinaryMatrix;
@@ -7,6 +7,8 @@
 //!
 //! Bit packing: big-endian within each byte (bit 0 = MSB).
 
+use crate::gf::hadamard::hadamard_transform;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReedMullerError {
     InvalidLength { expected: usize, got: usize },
@@ -82,7 +84,7 @@ impl ReedMuller {
         }
 
         // Walsh–Hadamard transform in-place.
-        hadamard_transform_i16(&mut f);
+        hadamard_transform(&mut f);
 
         // Find index with maximum absolute value. Tie-break by smaller index
         // (matches “smallest value in the lowest 7 bits” guidance). :contentReference[oaicite:3]{index=3}
@@ -158,21 +160,3 @@ fn set_bit_be(buf: &mut [u8], bit_index: usize, bit: u8) {
         buf[byte] &= !mask;
     }
 }
-
-/// In-place Walsh–Hadamard transform (length must be power-of-two).
-fn hadamard_transform_i16(v: &mut [i16]) {
-    debug_assert!(v.len().is_power_of_two());
-    let mut len = 1;
-    while len < v.len() {
-        let step = len << 1;
-        for i in (0..v.len()).step_by(step) {
-            for j in 0..len {
-                let a = v[i + j];
-                let b = v[i + j + len];
-                v[i + j] = a + b;
-                v[i + j + len] = a - b;
-            }
-        }
-        len = step;
-    }
-}
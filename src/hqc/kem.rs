@@ -1,4 +1,5 @@
 use super::hash::{G, H, I, J};
+use super::hqcgf2::HqcGf2;
 use super::params::HqcPkeParams;
 use super::pke;
 use super::types::{
@@ -104,3 +105,24 @@ pub fn decaps<P: HqcPkeParams>(dk: &DkKem<P>, c: &CiphKem<P>) -> SharedKey32 {
 
     k_p
 }
+
+/// `decaps` with the secret vector y instead of a decapsulation key. Without sigma there is
+/// no implicit-rejection key, so an invalid ciphertext gives None.
+pub fn decaps_with_secret_vector<P: HqcPkeParams>(
+    ek: &EkKem<P>,
+    y: &HqcGf2,
+    c: &CiphKem<P>,
+) -> Option<SharedKey32> {
+    let m_prime = pke::decrypt_with_secret_vector::<P>(y, &c.c_pke)?;
+    let ek_bytes = ek.to_bytes();
+    let h_ek = H(&[&ek_bytes]);
+    let g_out = G(&[&h_ek, &m_prime, &c.salt]);
+
+    let mut k_p = [0u8; 32];
+    let mut theta_p = [0u8; 32];
+    k_p.copy_from_slice(&g_out[..32]);
+    theta_p.copy_from_slice(&g_out[32..64]);
+
+    let c_prime = pke::encrypt::<P>(ek, &m_prime, theta_p).ok()?;
+    ct_eq(&c_prime.to_bytes(), &c.c_pke.to_bytes()).then_some(k_p)
+}
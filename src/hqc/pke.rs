@@ -8,7 +8,7 @@ use super::types::{CiphPke, DkPke, EkPke, Seed32, TypesError};
 use super::concatenated_codes::reed_solomon::ReedSolomon;
 use super::concatenated_codes::RmrsCode;
 
-fn rmrs_codec<P: HqcPkeParams>() -> RmrsCode {
+pub(crate) fn rmrs_codec<P: HqcPkeParams>() -> RmrsCode {
     let rs = ReedSolomon::new(P::N1, P::K_BYTES, P::RS_GEN_POLY);
     RmrsCode::new(rs, P::RM_MULT)
 }
@@ -37,6 +37,14 @@ pub fn keygen<P: HqcPkeParams>(seed_pke: Seed32) -> (EkPke<P>, DkPke<P>) {
     (ek, dk)
 }
 
+/// `(h, s)` of a public key, with h expanded from `seed_ek`.
+pub fn expand_public_key<P: HqcPkeParams>(ek: &EkPke<P>) -> (HqcGf2, HqcGf2) {
+    let ctx_ek = Shake256Xof::new(&ek.seed_ek);
+    let h = sample_vect(P::N, &ctx_ek);
+    let s_vec = HqcGf2::from_bytes_le_bits(P::N, &ek.s);
+    (h, s_vec)
+}
+
 pub fn encrypt<P: HqcPkeParams>(
     ek: &EkPke<P>,
     m: &[u8],
@@ -49,10 +57,7 @@ pub fn encrypt<P: HqcPkeParams>(
         });
     }
 
-    let ctx_ek = Shake256Xof::new(&ek.seed_ek);
-    let h = sample_vect(P::N, &ctx_ek);
-
-    let s_vec = HqcGf2::from_bytes_le_bits(P::N, &ek.s);
+    let (h, s_vec) = expand_public_key(ek);
 
     let ctx_theta = Shake256Xof::new(&theta);
     let r2 = sample_fixed_weight_vect(P::N, P::W_R, &ctx_theta);
@@ -82,10 +87,15 @@ pub fn decrypt<P: HqcPkeParams>(dk: &DkPke<P>, c: &CiphPke<P>) -> Option<Vec<u8>
     let ctx_dk = Shake256Xof::new(&dk.seed_dk);
     let y = sample_fixed_weight_vect(P::N, P::W, &ctx_dk);
     let _x = sample_fixed_weight_vect(P::N, P::W, &ctx_dk);
+    decrypt_with_secret_vector::<P>(&y, c)
+}
 
+/// `decrypt` with the secret vector y itself instead of `seed_dk`, e.g. a y recovered by an
+/// ISD attack on the public key.
+pub fn decrypt_with_secret_vector<P: HqcPkeParams>(y: &HqcGf2, c: &CiphPke<P>) -> Option<Vec<u8>> {
     let u_vec = HqcGf2::from_bytes_le_bits(P::N, &c.u);
     let v_vec = HqcGf2::from_bytes_le_bits(P::N1N2_BITS, &c.v);
-    let uy = u_vec.mul_bitpacked(&y);
+    let uy = u_vec.mul_bitpacked(y);
     let uy_trunc = uy.truncate(P::N1N2_BITS);
 
     let mut v_minus = v_vec;
@@ -0,0 +1,186 @@
+use super::*;
+use crate::isd::estimator::log2_binomial;
+use crate::hqc::hqcgf2::HqcGf2;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+/// Bernstein–Lange–Peters ball-collision decoding on `[h | I]`.
+///
+/// Like `Stern`, but each side of the collision may also put up to q errors on its half of the
+/// l window columns: the left list enumerates p columns of the first information-set half
+/// together with a ball of radius q in the first window half, the right list the same for the
+/// second halves. `q = 0` is Stern.
+pub struct BallCollision {
+    pub p: usize,
+    pub q: usize,
+    pub l: usize,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+impl BallCollision {
+    pub fn new(p: usize, q: usize, l: usize, max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self { p, q, l, max_iters, seed }
+    }
+}
+
+impl Default for BallCollision {
+    fn default() -> Self {
+        Self {
+            p: 1,
+            q: 1,
+            l: 16,
+            max_iters: Some(200000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for BallCollision {
+    fn name(&self) -> &'static str {
+        "Ball-collision"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for BallCollision {
+    fn config(&self) -> String {
+        format!("p={} q={} l={}", self.p, self.q, self.l)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        if self.l > 64 || self.l > n {
+            return Err(AttackError::InvalidParameter(
+                "parameter l must be <= 64 and <= n".to_string(),
+            ));
+        }
+        if self.p + self.q > w || self.p > n / 2 || self.q > self.l / 2 {
+            return Err(AttackError::InvalidParameter(
+                "parameters must satisfy p + q <= w, p <= n / 2 and q <= l / 2".to_string(),
+            ));
+        }
+
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let max_iters = self.max_iters.unwrap_or(u64::MAX);
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        let mut form = SemiSystematicForm::new(n, self.l);
+        cursor.restore_perm(&mut form.perm)?;
+        let ball: f64 = (0..=self.q).map(|r| log2_binomial(self.l / 2, r).exp2()).sum();
+        let entries = log2_binomial(n / 2, self.p).exp2() * ball;
+        if !stats.table_fits(budget, entries, table_entry_bytes(self.p + self.q)) {
+            return Ok(None);
+        }
+
+        loop {
+            cursor.mark(&rng, &form.perm);
+            if stats.iterations >= max_iters {
+                break;
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            if !stats.record_elimination(form.reduce(&mut rng, h, s)) {
+                continue;
+            }
+            if let Some(y) = ball_collide(&form, self.p, self.q, w, h, s, stats) {
+                return Ok(Some(y));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Enumerate `p` columns of `info_off..info_off + info_len` together with up to `q` columns
+/// of `win_off..win_off + win_len`, calling `f` with the combined non-pivot support.
+fn for_each_ball(
+    (info_off, info_len, p): (usize, usize, usize),
+    (win_off, win_len, q): (usize, usize, usize),
+    mut f: impl FnMut(&[usize]) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let mut cols = Vec::with_capacity(p + q);
+    for_each_combination_cf(info_len, p, |info| {
+        for radius in 0..=q {
+            for_each_combination_cf(win_len, radius, |win| {
+                cols.clear();
+                cols.extend(info.iter().map(|&i| info_off + i));
+                cols.extend(win.iter().map(|&i| win_off + i));
+                f(&cols)
+            })?;
+        }
+        ControlFlow::Continue(())
+    })
+}
+
+/// Collide the two balls on the window bits.
+pub fn ball_collide(
+    form: &SemiSystematicForm,
+    p: usize,
+    q: usize,
+    w: usize,
+    h: &HqcGf2,
+    s: &HqcGf2,
+    stats: &mut AttackStats,
+) -> Option<HqcGf2> {
+    let l = form.l;
+    let (l1, n1) = (l / 2, form.info_len() / 2);
+    let (l2, n2) = (l - l1, form.info_len() - n1);
+    let mut table: HashMap<u64, Vec<Vec<usize>>> = HashMap::new();
+    let mut entries = 0;
+    let _ = for_each_ball((l, n1, p), (0, l1, q), |cols| {
+        table.entry(form.key_of(cols)).or_default().push(cols.to_vec());
+        entries += 1;
+        ControlFlow::Continue(())
+    });
+    stats.record_table(entries);
+
+    let mut answer: Option<HqcGf2> = None;
+    let mut cand = Vec::with_capacity(2 * (p + q));
+    let _ = for_each_ball((l + n1, n2, p), (l1, l2, q), |cols| {
+        let key = form.rhs_key ^ form.key_of(cols);
+        if let Some(lefts) = table.get(&key) {
+            for left in lefts {
+                cand.clear();
+                cand.extend_from_slice(cols);
+                cand.extend_from_slice(left);
+                stats.candidate_checks += 1;
+                if let Some(y) = form.recover(&cand, w, h, s) {
+                    answer = Some(y);
+                    return ControlFlow::Break(());
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    });
+    answer
+}
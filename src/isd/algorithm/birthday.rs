@@ -0,0 +1,265 @@
+use super::*;
+use crate::isd::estimator::log2_binomial;
+use crate::isd::verify::is_solution;
+use crate::hqc::hash::xof::Shake256Xof;
+use crate::hqc::hqcgf2::HqcGf2;
+use crate::hqc::sampling::rand_bits;
+use std::collections::HashSet;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+/// Birthday search on y: splits the support of y over the two halves of `[0, n)` and
+/// matches `(h·y)_J` against `(s + h·y)_J` on a random window J of positions.
+/// No information set is computed, so errors in x are never enumerated.
+pub struct BirthdaySearch {
+    window_size: usize,
+    bound: usize,
+    window_tries: usize,
+    seed: Vec<u8>,
+    cap_per_key: usize,
+}
+
+impl BirthdaySearch {
+    pub fn new(window_size: usize, bound: usize, window_tries: usize, seed: Vec<u8>, cap_per_key: usize) -> Self {
+        assert!(window_size > 0, "window_size must be positive");
+        Self {
+            window_size,
+            bound,
+            window_tries,
+            seed,
+            cap_per_key,
+        }
+    }
+}
+
+impl Default for BirthdaySearch {
+    fn default() -> Self {
+        Self {
+            window_size: 100,
+            bound: 4,
+            window_tries: 100,
+            seed: "default_seed".as_bytes().to_vec(),
+            cap_per_key: 100,
+        }
+    }
+}
+
+impl Attack for BirthdaySearch {
+    fn name(&self) -> &'static str {
+        "Birthday Search"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for BirthdaySearch {
+    fn config(&self) -> String {
+        format!(
+            "window_size={} bound={} seed={} cap_per_key={}",
+            self.window_size,
+            self.bound,
+            hex::encode(&self.seed),
+            self.cap_per_key
+        )
+    }
+
+    /// The checkpoint position is `[window index, delta, sign]` of the next attempt.
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        if self.window_size == 0 || self.window_size > n - w {
+            return Err(AttackError::InvalidParameter(
+                "parameter window_size must be > 0 and <= n - w".to_string(),
+            ));
+        }
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let start = match cursor.position[..] {
+            [] => [0, 0, 0],
+            [i, delta, sign] => [i, delta, sign],
+            _ => {
+                return Err(AttackError::InvalidParameter(
+                    "checkpoint position must be [window, delta, sign]".to_string(),
+                ));
+            }
+        };
+        for i in start[0] as usize..self.window_tries {
+            let mut seed = Vec::with_capacity(self.seed.len() + 8);
+            seed.extend_from_slice(&self.seed);
+            seed.extend_from_slice(&(i as u64).to_le_bytes());
+            let window = select_window(n, self.window_size, &seed);
+            let mid = w / 2;
+            for delta in 0..=self.bound {
+                for sign in [0i32, 1i32] {
+                    let p1 = match sign {
+                        0 => mid.saturating_add(delta),
+                        _ => mid.saturating_sub(delta),
+                    };
+                    if p1 > w {
+                        continue;
+                    }
+                    let p2 = w - p1;
+                    let n1 = n / 2;
+                    let n2 = n - n1;
+                    if p1 > n1 || p2 > n2 {
+                        continue;
+                    }
+                    let position = [i as u64, delta as u64, sign as u64];
+                    if position < start {
+                        continue;
+                    }
+                    cursor.position = position.to_vec();
+                    if stats.should_stop(budget) {
+                        return Ok(None);
+                    }
+                    let entries = log2_binomial(n1, p1).exp2();
+                    if !stats.table_fits(budget, entries, table_entry_bytes(p1)) {
+                        return Ok(None);
+                    }
+                    stats.iterations += 1;
+                    if let Some(y) = birthday_try_once(
+                        n, w, h, s, n1, n2, p1, p2, &window, self.cap_per_key, stats,
+                    ) {
+                        return Ok(Some(y));
+                    }
+                }
+            }
+        }
+        cursor.position = vec![self.window_tries as u64, 0, 0];
+        Ok(None)
+    }
+}
+
+/// Select a random integer in [0, bound)
+fn rand_bounded(xof: &Shake256Xof, bound: usize) -> usize {
+    debug_assert!(bound > 0);
+    let b = bound as u64;
+    let limit = (u64::MAX / b) * b;
+    loop {
+        let r = rand_bits(xof) as u64;
+        if r < limit {
+            return (r % b) as usize;
+        }
+    }
+}
+
+/// Select a random window of given size
+pub fn select_window(n: usize, window_size: usize, seed: &[u8]) -> Vec<usize> {
+    assert!(window_size <= n, "window_size must be <= n");
+    let xof = Shake256Xof::new(seed);
+    let k = window_size;
+    let mut chosen: HashSet<usize> = HashSet::with_capacity(k * 2);
+    for j in (n - k)..n {
+        let t = rand_bounded(&xof, j + 1);
+        if !chosen.insert(t) {
+            chosen.insert(j);
+        }
+    }
+    let mut window: Vec<usize> = chosen.into_iter().collect();
+    window.sort_unstable();
+    window
+}
+
+pub fn birthday_try_once(
+    n: usize,
+    w: usize,
+    h: &HqcGf2,
+    s: &HqcGf2,
+    n1: usize,
+    n2: usize,
+    p1: usize,
+    p2: usize,
+    window: &[usize],
+    cap_per_key: usize,
+    stats: &mut AttackStats,
+) -> Option<HqcGf2> {
+    if n1 + n2 != n {
+        return None;
+    }
+    if p1 > n1 || p2 > n2 {
+        return None;
+    }
+    let keys = WindowColumnKeys::new(n, h, window);
+    let key_words = keys.key_words();
+    let mut s_key = vec![0u64; key_words];
+    for (i, &pos) in window.iter().enumerate() {
+        if s.get(pos) {
+            s_key[i / 64] |= 1u64 << (i % 64);
+        }
+    }
+    let mut key_buf = vec![0u64; key_words];
+    let mut rhs_buf = vec![0u64; key_words];
+    let mut table: HashMap<Vec<u64>, Vec<Vec<usize>>> = HashMap::new();
+    let mut entries = 0;
+    let _ = for_each_combination_revolving_cf(n1, p1, |support_y1, swap| {
+        match swap {
+            None => keys.key_of(support_y1.iter().copied(), &mut key_buf),
+            Some((left, entered)) => {
+                keys.toggle(left, &mut key_buf);
+                keys.toggle(entered, &mut key_buf);
+            }
+        }
+
+        let entry = table.entry(key_buf.clone()).or_default();
+        if entry.len() < cap_per_key {
+            entry.push(support_y1.to_vec());
+            entries += 1;
+        }
+
+        ControlFlow::Continue(())
+    });
+    stats.record_table(entries);
+
+    let mut answer: Option<HqcGf2> = None;
+    let _ = for_each_combination_revolving_cf(n2, p2, |support_y2, swap| {
+        match swap {
+            None => keys.key_of(support_y2.iter().map(|&i| n1 + i), &mut key_buf),
+            Some((left, entered)) => {
+                keys.toggle(n1 + left, &mut key_buf);
+                keys.toggle(n1 + entered, &mut key_buf);
+            }
+        }
+        for ((r, k), sk) in rhs_buf.iter_mut().zip(&key_buf).zip(&s_key) {
+            *r = k ^ sk;
+        }
+
+        if let Some(cands) = table.get(&rhs_buf) {
+            for y1 in cands {
+                let mut supp_y = Vec::with_capacity(y1.len() + support_y2.len());
+                supp_y.extend_from_slice(y1);
+                supp_y.extend(support_y2.iter().map(|&i| n1 + i));
+                stats.candidate_checks += 1;
+                let y = HqcGf2::from_indices(n, &supp_y);
+                if is_solution(w, h, s, &y) {
+                    answer = Some(y);
+                    return ControlFlow::Break(());
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    });
+    answer
+}
\ No newline at end of file
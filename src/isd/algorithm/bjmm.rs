@@ -0,0 +1,297 @@
+use super::*;
+use crate::isd::estimator::log2_binomial;
+use crate::hqc::hqcgf2::HqcGf2;
+use rand::Rng;
+use std::ops::ControlFlow;
+
+/// Becker–Joux–May–Meurer ISD on `[h | I]`.
+///
+/// Works on the same semi-systematic form as `Stern`, but looks for the weight-p part of the
+/// error on all `n + l` non-pivot columns as a sum `e1 + e2` of two vectors of weight
+/// `p/2 + ε` (the ε overlapping ones cancel, 1 + 1 = 0). The many representations of each
+/// solution allow both lists to be filtered on a random target of the window bits.
+/// `depth = 2` uses one representation level, `depth = 3` two; the bottom lists are built by
+/// disjoint meet-in-the-middle over the two halves of the non-pivot columns.
+pub struct Bjmm {
+    pub depth: usize,
+    pub p: usize,
+    pub l: usize,
+    /// ε per representation level, top first; `eps.len() == depth - 1`.
+    pub eps: Vec<usize>,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+impl Bjmm {
+    pub fn new(
+        depth: usize,
+        p: usize,
+        l: usize,
+        eps: Vec<usize>,
+        max_iters: Option<u64>,
+        seed: Option<u64>,
+    ) -> Self {
+        Self { depth, p, l, eps, max_iters, seed }
+    }
+}
+
+impl Default for Bjmm {
+    fn default() -> Self {
+        Self {
+            depth: 2,
+            p: 2,
+            l: 16,
+            eps: vec![1],
+            max_iters: Some(200000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for Bjmm {
+    fn name(&self) -> &'static str {
+        "BJMM"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for Bjmm {
+    fn config(&self) -> String {
+        format!("depth={} p={} l={} eps={:?}", self.depth, self.p, self.l, self.eps)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        if self.depth != 2 && self.depth != 3 {
+            return Err(AttackError::InvalidParameter(
+                "parameter depth must be 2 or 3".to_string(),
+            ));
+        }
+        if self.eps.len() != self.depth - 1 {
+            return Err(AttackError::InvalidParameter(
+                "parameter eps must have depth - 1 entries".to_string(),
+            ));
+        }
+        checkpoint.claim(self, n, w)?;
+        run_representation_attack(
+            n, w, h, s, self.p, self.l, &self.eps, self.max_iters, self.seed, budget, checkpoint,
+        )
+    }
+}
+
+/// Shared driver for the representation-based attacks (`Bjmm`, `Mmt`).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn run_representation_attack(
+    n: usize,
+    w: usize,
+    h: &HqcGf2,
+    s: &HqcGf2,
+    p: usize,
+    l: usize,
+    eps: &[usize],
+    max_iters: Option<u64>,
+    seed: Option<u64>,
+    budget: &AttackBudget,
+    checkpoint: &mut Checkpoint,
+) -> Result<Option<HqcGf2>, AttackError> {
+    if l > 64 || l > n {
+        return Err(AttackError::InvalidParameter(
+            "parameter l must be <= 64 and <= n".to_string(),
+        ));
+    }
+    if p > 2 * w || p > n - l {
+        return Err(AttackError::InvalidParameter(
+            "parameter p must be <= 2w and <= n - l".to_string(),
+        ));
+    }
+    let tree = MergeTree::new(n + l, l, p, eps);
+    if tree.leaf_weight_too_large() {
+        return Err(AttackError::InvalidParameter(
+            "p + eps too large for the number of non-pivot columns".to_string(),
+        ));
+    }
+
+    let Checkpoint { cursor, stats, .. } = checkpoint;
+    let max_iters = max_iters.unwrap_or(u64::MAX);
+    let mut rng = cursor.rng(seed);
+    stats.seed = cursor.seed;
+    let mut form = SemiSystematicForm::new(n, l);
+    cursor.restore_perm(&mut form.perm)?;
+    if !stats.table_fits(budget, tree.expected_max_list_len(), table_entry_bytes(p)) {
+        return Ok(None);
+    }
+
+    loop {
+        cursor.mark(&rng, &form.perm);
+        if stats.iterations >= max_iters {
+            break;
+        }
+        if stats.should_stop(budget) {
+            return Ok(None);
+        }
+        stats.iterations += 1;
+        if !stats.record_elimination(form.reduce(&mut rng, h, s)) {
+            continue;
+        }
+        let mut answer = None;
+        let mut checks = 0;
+        let _ = tree.for_each_solution(&form, &mut rng, stats, |cols| {
+            checks += 1;
+            answer = form.recover(cols, w, h, s);
+            match answer {
+                Some(_) => ControlFlow::Break(()),
+                None => ControlFlow::Continue(()),
+            }
+        });
+        stats.candidate_checks += checks;
+        if answer.is_some() {
+            return Ok(answer);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Representation merge tree over the non-pivot columns of a `SemiSystematicForm`.
+///
+/// Level 0 is the root (weight p, all l window bits). A node of weight `v` at level `i` has
+/// children of weights `ceil(v/2) + eps[i]` and `floor(v/2) + eps[i]`. Level `i > 0` nodes are
+/// filtered on the low `bits[i]` window bits, where `bits[i]` is about log2 of the number of
+/// representations of a level `i - 1` vector.
+pub struct MergeTree {
+    pub cols: usize,
+    pub p: usize,
+    pub eps: Vec<usize>,
+    pub bits: Vec<usize>,
+}
+
+impl MergeTree {
+    pub fn new(cols: usize, l: usize, p: usize, eps: &[usize]) -> Self {
+        let mut bits = vec![l];
+        let mut weight = p;
+        for &e in eps {
+            let reps = log2_binomial(weight, weight.div_ceil(2))
+                + log2_binomial(cols.saturating_sub(weight), e);
+            let prev = *bits.last().expect("non-empty");
+            bits.push((reps.floor().max(0.0) as usize).min(prev));
+            weight = weight.div_ceil(2) + e;
+        }
+        Self { cols, p, eps: eps.to_vec(), bits }
+    }
+
+    /// Expected length of the largest list built per iteration: the bottom lists, or a
+    /// merged level-i list of about `C(cols, weight_i) / 2^bits[i]` entries.
+    pub fn expected_max_list_len(&self) -> f64 {
+        let mut weight = self.p;
+        let mut largest = 0.0f64;
+        for (i, &e) in self.eps.iter().enumerate() {
+            weight = weight.div_ceil(2) + e;
+            let merged = log2_binomial(self.cols, weight) - self.bits[i + 1] as f64;
+            largest = largest.max(merged.exp2());
+        }
+        let half = self.cols / 2;
+        let leaf = log2_binomial(half, weight.div_ceil(2)).max(log2_binomial(self.cols - half, weight / 2));
+        largest.max(leaf.exp2())
+    }
+
+    fn leaf_weight_too_large(&self) -> bool {
+        let mut weight = self.p;
+        for &e in &self.eps {
+            weight = weight.div_ceil(2) + e;
+        }
+        weight.div_ceil(2) > self.cols / 2
+    }
+
+    /// Calls `f` with the sorted non-pivot support of every root element whose key matches
+    /// `form.rhs_key` on all l window bits. Every list built is recorded in `stats`.
+    pub fn for_each_solution<R: Rng>(
+        &self,
+        form: &SemiSystematicForm,
+        rng: &mut R,
+        stats: &mut AttackStats,
+        mut f: impl FnMut(&[usize]) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let (mut left, mut right) = self.children(form, rng, stats, 0, self.p, form.rhs_key);
+        let p = self.p;
+        join_on_mask(&mut left, &mut right, low_mask(self.bits[0]), form.rhs_key, |a, b| {
+            let cols = xor_supports(&a.cols, &b.cols);
+            if cols.len() == p { f(&cols) } else { ControlFlow::Continue(()) }
+        })
+    }
+
+    fn children<R: Rng>(
+        &self,
+        form: &SemiSystematicForm,
+        rng: &mut R,
+        stats: &mut AttackStats,
+        level: usize,
+        weight: usize,
+        target: u64,
+    ) -> (Vec<ListEntry>, Vec<ListEntry>) {
+        if level == self.eps.len() {
+            let half = self.cols / 2;
+            let left = list_of_combinations(form, 0, half, weight.div_ceil(2));
+            let right = list_of_combinations(form, half, self.cols - half, weight / 2);
+            stats.record_table(left.len());
+            stats.record_table(right.len());
+            return (left, right);
+        }
+        let e = self.eps[level];
+        let child_mask = low_mask(self.bits[level + 1]);
+        let t_left = rng.r#gen::<u64>() & child_mask;
+        let t_right = (target ^ t_left) & child_mask;
+        let left = self.node(form, rng, stats, level + 1, weight.div_ceil(2) + e, t_left);
+        let right = self.node(form, rng, stats, level + 1, weight / 2 + e, t_right);
+        (left, right)
+    }
+
+    fn node<R: Rng>(
+        &self,
+        form: &SemiSystematicForm,
+        rng: &mut R,
+        stats: &mut AttackStats,
+        level: usize,
+        weight: usize,
+        target: u64,
+    ) -> Vec<ListEntry> {
+        let (mut left, mut right) = self.children(form, rng, stats, level, weight, target);
+        let mut out = Vec::new();
+        let _ = join_on_mask(&mut left, &mut right, low_mask(self.bits[level]), target, |a, b| {
+            let cols = xor_supports(&a.cols, &b.cols);
+            if cols.len() == weight {
+                out.push(ListEntry { key: a.key ^ b.key, cols });
+            }
+            ControlFlow::Continue(())
+        });
+        out.sort_unstable_by(|a, b| a.cols.cmp(&b.cols));
+        out.dedup_by(|a, b| a.cols == b.cols);
+        stats.record_table(out.len());
+        out
+    }
+}
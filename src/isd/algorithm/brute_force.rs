@@ -1,6 +1,15 @@
-use super::{Attack, AttackError};
+use super::helper::{hqc_column_into, revolving_door_next};
+use super::{solve_fresh, Attack, AttackBudget, AttackError, AttackStats, Checkpoint, Resumable};
+use crate::gf::gf2::Gf2Construct;
 use crate::hqc::hqcgf2::HqcGf2;
+use crate::isd::instance::{SdAttack, SdInstance};
+use crate::isd::verify::is_solution;
 
+/// Exhaustive search. On `[h | I]` only y is enumerated, since it determines x; on a generic
+/// `SdInstance` every weight-t support of e is tried.
+///
+/// Supports are visited in revolving-door order, so each step swaps one index and `h·y` (or
+/// `H·e`) is updated with two column XORs instead of a full product.
 pub struct BruteForce {
     pub max_iters: Option<u64>,
 }
@@ -15,60 +24,141 @@ impl Attack for BruteForce {
     fn name(&self) -> &'static str {
         "Brute Force"
     }
-    fn solve(
+    fn solve_within(
         &self,
         n: usize,
         w: usize,
         h: &HqcGf2,
         s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
     ) -> Result<Option<HqcGf2>, AttackError> {
-        let mut iters: u64 = 0;
-        let mut comb: Vec<usize> = (0..w).collect();
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
 
-        loop {
-            if let Some(cap) = self.max_iters {
-                if iters >= cap {
-                    return Ok(None);
-                }
-            }
-            iters += 1;
+/// Marks an enumeration that has run through every combination.
+const EXHAUSTED: u64 = u64::MAX;
 
-            let y = HqcGf2::from_indices(n, &comb);
+impl Resumable for BruteForce {
+    fn config(&self) -> String {
+        "order=revolving-door".to_string()
+    }
 
-            let hy = y.mul_bitpacked(h);
+    /// The checkpoint position is the support of the next y to try, in revolving-door order.
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let mut comb: Vec<usize> = match &cursor.position[..] {
+            [] => (0..w).collect(),
+            [EXHAUSTED] => return Ok(None),
+            pos if is_support(pos, n, w) => pos.iter().map(|&c| c as usize).collect(),
+            _ => {
+                return Err(AttackError::InvalidParameter(
+                    "checkpoint position is not a weight-w support".to_string(),
+                ));
+            }
+        };
 
-            let mut x = s.clone();
-            x.xor_in_place(&hy);
+        let mut col = HqcGf2::zero_with_len(n);
+        let mut tmp = vec![0u64; HqcGf2::word_len(n)];
+        // x = s + h·y, kept up to date as y changes.
+        let mut x = s.clone();
+        for &c in &comb {
+            hqc_column_into(h, c, &mut col, &mut tmp);
+            x.xor_in_place(&col);
+        }
+        loop {
+            cursor.position = comb.iter().map(|&c| c as u64).collect();
+            if self.max_iters.is_some_and(|cap| stats.iterations >= cap) {
+                return Ok(None);
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            stats.candidate_checks += 1;
 
-            if (x.weight() as usize) == w {
-                return Ok(Some(y));
+            if x.weight() as usize == w {
+                let y = HqcGf2::from_indices(n, &comb);
+                if is_solution(w, h, s, &y) {
+                    return Ok(Some(y));
+                }
             }
 
-            if !next_combination(&mut comb, n) {
-                break;
+            let Some((left, entered)) = revolving_door_next(&mut comb, n) else {
+                cursor.position = vec![EXHAUSTED];
+                return Ok(None);
+            };
+            for c in [left, entered] {
+                hqc_column_into(h, c, &mut col, &mut tmp);
+                x.xor_in_place(&col);
             }
         }
-
-        Ok(None)
     }
 }
 
-fn next_combination(comb: &mut [usize], n: usize) -> bool {
-    let k = comb.len();
-    if k == 0 {
-        return false;
-    }
-
-    for i_rev in 0..k {
-        let i = k - 1 - i_rev;
-        let max_val = n - (k - i);
-        if comb[i] < max_val {
-            comb[i] += 1;
-            for j in (i + 1)..k {
-                comb[j] = comb[j - 1] + 1;
+impl SdAttack for BruteForce {
+    fn solve_instance<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        let (len, weight) = (instance.code_len(), instance.weight());
+        if weight > len {
+            return Ok(None);
+        }
+        let mut comb: Vec<usize> = (0..weight).collect();
+        let rows = instance.redundancy();
+        let mut col = HqcGf2::zero_with_len(rows);
+        let mut tmp = vec![0u64; HqcGf2::word_len(rows)];
+        // H·e, kept up to date as e changes.
+        let mut syndrome = HqcGf2::zero_with_len(rows);
+        for &c in &comb {
+            instance.column_into(c, &mut col, &mut tmp);
+            syndrome.xor_in_place(&col);
+        }
+        loop {
+            if self.max_iters.is_some_and(|cap| stats.iterations >= cap) {
+                return Ok(None);
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            stats.candidate_checks += 1;
+            if syndrome == *instance.syndrome() {
+                let e = HqcGf2::from_indices(len, &comb);
+                if instance.is_solution(&e) {
+                    return Ok(Some(e));
+                }
+            }
+            let Some((left, entered)) = revolving_door_next(&mut comb, len) else {
+                return Ok(None);
+            };
+            for c in [left, entered] {
+                instance.column_into(c, &mut col, &mut tmp);
+                syndrome.xor_in_place(&col);
             }
-            return true;
         }
     }
-    false
-}
\ No newline at end of file
+}
+
+/// Strictly increasing, `w` entries, all `< n`.
+fn is_support(pos: &[u64], n: usize, w: usize) -> bool {
+    pos.len() == w && pos.windows(2).all(|p| p[0] < p[1]) && pos.iter().all(|&c| (c as usize) < n)
+}
@@ -0,0 +1,187 @@
+use super::*;
+use crate::isd::estimator::log2_binomial;
+use crate::gf::gf2::Gf2Construct;
+use crate::hqc::hqcgf2::HqcGf2;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+/// Decoding One Out of Many for HQC key recovery.
+///
+/// `(h, X^i·s)` is a valid instance with solution `(X^i·y, X^i·x)` for every rotation i, so
+/// each elimination can be matched against all n rotated syndromes at once. With `p = 0` and
+/// `l = 0` this is Prange checking every rotation; otherwise the right Stern list is collided
+/// against each rotated window key. A hit is rotated back to the original y.
+pub struct Doom {
+    pub p: usize,
+    pub l: usize,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+impl Doom {
+    pub fn new(p: usize, l: usize, max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self { p, l, max_iters, seed }
+    }
+
+    /// DOOM on top of Prange.
+    pub fn prange(max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self::new(0, 0, max_iters, seed)
+    }
+
+    /// DOOM on top of Stern.
+    pub fn stern(p: usize, l: usize, max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self::new(p, l, max_iters, seed)
+    }
+}
+
+impl Default for Doom {
+    fn default() -> Self {
+        Self {
+            p: 1,
+            l: 12,
+            max_iters: Some(200000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for Doom {
+    fn name(&self) -> &'static str {
+        "DOOM"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for Doom {
+    fn config(&self) -> String {
+        format!("p={} l={}", self.p, self.l)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        if self.l > 64 || self.l > n {
+            return Err(AttackError::InvalidParameter(
+                "parameter l must be <= 64 and <= n".to_string(),
+            ));
+        }
+        if self.p > w || self.p > n / 2 {
+            return Err(AttackError::InvalidParameter(
+                "parameter p must be <= w and <= n / 2".to_string(),
+            ));
+        }
+
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let max_iters = self.max_iters.unwrap_or(u64::MAX);
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        let mut form = SemiSystematicForm::with_transform(n, self.l);
+        cursor.restore_perm(&mut form.perm)?;
+        let mut tmp_words: Vec<u64> = vec![0u64; HqcGf2::word_len(n)];
+        let entries = log2_binomial(n / 2, self.p).exp2();
+        if !stats.table_fits(budget, entries, table_entry_bytes(self.p)) {
+            return Ok(None);
+        }
+
+        loop {
+            cursor.mark(&rng, &form.perm);
+            if stats.iterations >= max_iters {
+                break;
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            if !stats.record_elimination(form.reduce(&mut rng, h, s)) {
+                continue;
+            }
+            if let Some((i, y_i)) = doom_collide(&form, self.p, w, h, s, &mut tmp_words, stats) {
+                let mut y = HqcGf2::zero_with_len(n);
+                y_i.rotate_right_into(i, &mut y, &mut tmp_words);
+                return Ok(Some(y));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Collide the left Stern list against the right list for every rotated syndrome.
+/// Some((i, y_i)) where `y_i` solves the instance with syndrome `X^i·s`.
+pub fn doom_collide(
+    form: &SemiSystematicForm,
+    p: usize,
+    w: usize,
+    h: &HqcGf2,
+    s: &HqcGf2,
+    tmp_words: &mut Vec<u64>,
+    stats: &mut AttackStats,
+) -> Option<(usize, HqcGf2)> {
+    let l = form.l;
+    let n = form.rows;
+    let n1 = n / 2;
+    let n2 = n - n1;
+    let rhs_keys = form.rotated_rhs_keys(s);
+    let mut table: HashMap<u64, Vec<Vec<usize>>> = HashMap::new();
+    let mut entries = 0;
+    let _ = for_each_combination_cf(n1, p, |support1| {
+        let cols: Vec<usize> = support1.iter().map(|&i| l + i).collect();
+        table.entry(form.key_of(&cols)).or_default().push(cols);
+        entries += 1;
+        ControlFlow::Continue(())
+    });
+    stats.record_table(entries);
+
+    let mut s_i = HqcGf2::zero_with_len(n);
+    let mut answer = None;
+    let mut cols = Vec::with_capacity(2 * p);
+    let _ = for_each_combination_cf(n2, p, |support2| {
+        cols.clear();
+        cols.extend(support2.iter().map(|&i| l + n1 + i));
+        let key2 = form.key_of(&cols);
+        for (i, &rhs_key) in rhs_keys.iter().enumerate() {
+            let Some(cands) = table.get(&(rhs_key ^ key2)) else {
+                continue;
+            };
+            s.rotate_left_into(i, &mut s_i, tmp_words);
+            let (rhs_top, rhs_key) = form.reduce_syndrome(&s_i);
+            for cols1 in cands {
+                cols.truncate(p);
+                cols.extend_from_slice(cols1);
+                stats.candidate_checks += 1;
+                if let Some(y_i) = form.recover_with(&cols, &rhs_top, rhs_key, w, h, &s_i) {
+                    answer = Some((i, y_i));
+                    return ControlFlow::Break(());
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    });
+    answer
+}
@@ -0,0 +1,195 @@
+use super::*;
+use crate::isd::estimator::log2_binomial;
+use crate::hqc::hqcgf2::HqcGf2;
+use crate::isd::instance::{QcDoubleCirculant, SdAttack, SdInstance};
+use std::ops::ControlFlow;
+
+/// Dumer's ISD on `[h | I]`.
+///
+/// Each iteration permutes the 2n columns and reduces `n - l` of them to identity. Unlike
+/// Stern, the `l` window columns join the information set: the `k + l` non-pivot columns are
+/// split into two halves, p-subsets of each half are listed with their l-bit window keys, and
+/// the two lists are sorted and merged on `key_1 ^ key_2 = rhs_key`. The remaining `2w - 2p`
+/// errors must sit on the `n - l` pivot columns.
+///
+/// This is the variant the MMT and BJMM papers, and the HQC specification, take as their
+/// baseline, and the one `IsdAlgorithm::Dumer` estimates.
+pub struct Dumer {
+    pub p: usize,
+    pub l: usize,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+impl Dumer {
+    pub fn new(p: usize, l: usize, max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self { p, l, max_iters, seed }
+    }
+}
+
+impl Default for Dumer {
+    fn default() -> Self {
+        Self {
+            p: 1,
+            l: 12,
+            max_iters: Some(200000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for Dumer {
+    fn name(&self) -> &'static str {
+        "Dumer"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for Dumer {
+    fn config(&self) -> String {
+        format!("p={} l={}", self.p, self.l)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let instance = QcDoubleCirculant::new(h.clone(), s.clone(), w)?;
+        let e = self.run(&instance, budget, cursor, stats)?;
+        Ok(e.map(|e| instance.y_of(&e)))
+    }
+}
+
+impl SdAttack for Dumer {
+    fn solve_instance<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        self.run(instance, budget, &mut Cursor::default(), stats)
+    }
+}
+
+impl Dumer {
+    fn run<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        cursor: &mut Cursor,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        let rows = instance.redundancy();
+        if self.l > 64 || self.l > rows {
+            return Err(AttackError::InvalidParameter(
+                "parameter l must be <= 64 and <= n - k".to_string(),
+            ));
+        }
+        let len = instance.code_len() - rows + self.l;
+        let half = len / 2;
+        if 2 * self.p > instance.weight() || self.p > half {
+            return Err(AttackError::InvalidParameter(
+                "parameter p must be <= weight / 2 and <= (k + l) / 2".to_string(),
+            ));
+        }
+        let max_iters = self.max_iters.unwrap_or(u64::MAX);
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        let mut form = SemiSystematicForm::for_instance(instance, self.l);
+        cursor.restore_perm(&mut form.perm)?;
+        let entries = log2_binomial(half, self.p).exp2() + log2_binomial(len - half, self.p).exp2();
+        if !stats.table_fits(budget, entries, table_entry_bytes(self.p)) {
+            return Ok(None);
+        }
+
+        loop {
+            cursor.mark(&rng, &form.perm);
+            if stats.iterations >= max_iters {
+                break;
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            if !stats.record_elimination(form.reduce_instance(&mut rng, instance)) {
+                continue;
+            }
+            let found = dumer_merge_with(&form, self.p, stats, |cols| form.recover_error(cols, instance));
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// List the p-subsets of both halves of the `k + l` non-pivot columns and merge them on the
+/// window: every pair whose keys sum to `rhs_key` is handed to `recover`.
+pub fn dumer_merge(
+    form: &SemiSystematicForm,
+    p: usize,
+    w: usize,
+    h: &HqcGf2,
+    s: &HqcGf2,
+    stats: &mut AttackStats,
+) -> Option<HqcGf2> {
+    dumer_merge_with(form, p, stats, |cols| form.recover(cols, w, h, s))
+}
+
+/// `dumer_merge` with the candidate check `recover` on the non-pivot columns of each match.
+pub fn dumer_merge_with<T>(
+    form: &SemiSystematicForm,
+    p: usize,
+    stats: &mut AttackStats,
+    mut recover: impl FnMut(&[usize]) -> Option<T>,
+) -> Option<T> {
+    let len = form.non_pivot_len();
+    let n1 = len / 2;
+    let mut left = list_of_combinations(form, 0, n1, p);
+    let mut right = list_of_combinations(form, n1, len - n1, p);
+    stats.record_table(left.len() + right.len());
+
+    let mut answer = None;
+    let mut cols = Vec::with_capacity(2 * p);
+    let _ = join_on_mask(&mut left, &mut right, low_mask(form.l), form.rhs_key, |a, b| {
+        cols.clear();
+        cols.extend_from_slice(&a.cols);
+        cols.extend_from_slice(&b.cols);
+        stats.candidate_checks += 1;
+        match recover(&cols) {
+            Some(found) => {
+                answer = Some(found);
+                ControlFlow::Break(())
+            }
+            None => ControlFlow::Continue(()),
+        }
+    });
+    answer
+}
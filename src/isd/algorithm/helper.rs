@@ -1,6 +1,10 @@
 use crate::hqc::hqcgf2::HqcGf2;
 use rand::Rng;
+use rand::seq::SliceRandom;
 use crate::gf::gf2::Gf2Construct;
+use crate::gf::binary_matrix::BinaryMatrix;
+use crate::isd::instance::SdInstance;
+use crate::isd::verify::check_pair;
 use std::ops::ControlFlow;
 //prange
 /// true if successful, false if not invertible
@@ -10,15 +14,18 @@ pub fn gaussian_elimination_for_isd_instance(mat_rows: &mut [HqcGf2], rhs: &mut
     for (i, row) in mat_rows.iter().enumerate() {
         assert_eq!(row.n, n, "matrix must be square: row {i} has row.n != n");
     }
-    for col in 0..n {
-        let mut pivot = None;
-        for r in col..n {
-            if mat_rows[r].get(col) {
-                pivot = Some(r);
-                break;
-            }
-        }
-        let pivot = match pivot {
+    partial_gaussian_elimination(mat_rows, rhs, n)
+}
+
+/// Reduce the first `pivots` columns of a (rows x cols) matrix to identity on rows `0..pivots`.
+/// The remaining rows end up zero on those columns; `rhs` receives the same row operations.
+/// true if successful, false if those columns are linearly dependent
+pub fn partial_gaussian_elimination(mat_rows: &mut [HqcGf2], rhs: &mut HqcGf2, pivots: usize) -> bool {
+    let rows = mat_rows.len();
+    assert_eq!(rhs.n, rows, "rhs length mismatch: rhs.n != rows");
+    assert!(pivots <= rows, "pivots must be <= rows");
+    for col in 0..pivots {
+        let pivot = match (col..rows).find(|&r| mat_rows[r].get(col)) {
             Some(r) => r,
             None => return false,
         };
@@ -28,12 +35,13 @@ pub fn gaussian_elimination_for_isd_instance(mat_rows: &mut [HqcGf2], rhs: &mut
             rhs.swap_bits(pivot, col);
         }
         let (left, right) = mat_rows.split_at_mut(col);
-        let (pivot_row_mut, right_rest) = right.split_first_mut().expect("col < n so non-empty");
+        let (pivot_row_mut, right_rest) = right.split_first_mut().expect("col < rows so non-empty");
         let pivot_row: &HqcGf2 = &*pivot_row_mut;
-        for r in 0..col {
-            if left[r].get(col) {
-                left[r].xor_in_place(pivot_row);
-                if rhs.get(col) {
+        let rhs_bit = rhs.get(col);
+        for (r, row) in left.iter_mut().enumerate() {
+            if row.get(col) {
+                row.xor_in_place(pivot_row);
+                if rhs_bit {
                     rhs.toggle(r);
                 }
             }
@@ -42,7 +50,7 @@ pub fn gaussian_elimination_for_isd_instance(mat_rows: &mut [HqcGf2], rhs: &mut
             let r = col + 1 + k;
             if row.get(col) {
                 row.xor_in_place(pivot_row);
-                if rhs.get(col) {
+                if rhs_bit {
                     rhs.toggle(r);
                 }
             }
@@ -52,10 +60,31 @@ pub fn gaussian_elimination_for_isd_instance(mat_rows: &mut [HqcGf2], rhs: &mut
     true
 }
 
+/// One Canteaut–Chabaud step on a matrix whose first `rows` columns are the identity:
+/// pivot on entry (k, j) of the non-pivot column j, then swap columns k and j so the first
+/// `rows` columns are the identity again. `perm` records the column order.
+/// false (nothing changed) if entry (k, j) is zero.
+pub fn pivot_column_swap(mat: &mut BinaryMatrix, perm: &mut [usize], k: usize, j: usize) -> bool {
+    let rows = mat.rows();
+    debug_assert!(k < rows && j >= rows);
+    if !mat.get(k, j) {
+        return false;
+    }
+    for r in 0..rows {
+        if r != k && mat.get(r, j) {
+            mat.xor_rows(r, k);
+        }
+    }
+    mat.swap_cols(k, j);
+    perm.swap(k, j);
+    true
+}
+
+/// Move a uniformly random n-subset of `perm` to its front (n of 2n columns for `[h | I]`).
 #[inline]
 pub fn sample_cols<R: Rng>(rng: &mut R, perm: &mut [usize], n: usize) {
-    let total = 2 * n;
-    debug_assert_eq!(perm.len(), total);
+    let total = perm.len();
+    debug_assert!(n <= total);
     for i in 0..n {
         let j = rng.gen_range(i..total);
         perm.swap(i, j);
@@ -92,7 +121,22 @@ pub fn build_square_matrix_from_selected_columns(
     tmp_words: &mut Vec<u64>,
 ) {
     debug_assert_eq!(cols.len(), n);
+    build_matrix_from_selected_columns(n, h, cols, mat_rows, col_buf, tmp_words);
+}
+
+/// Same as `build_square_matrix_from_selected_columns`, but for any number of columns:
+/// column `k` of `mat_rows` is column `cols[k]` of `[h | I]`. Rows may be wider than
+/// `cols.len()`; the extra bits are cleared.
+pub fn build_matrix_from_selected_columns(
+    n: usize,
+    h: &HqcGf2,
+    cols: &[usize],
+    mat_rows: &mut [HqcGf2],
+    col_buf: &mut HqcGf2,
+    tmp_words: &mut Vec<u64>,
+) {
     debug_assert_eq!(mat_rows.len(), n);
+    debug_assert!(mat_rows.iter().all(|r| r.n >= cols.len()));
     debug_assert_eq!(col_buf.n, n);
     clear_matrix_rows(mat_rows);
     for (k, &col) in cols.iter().enumerate() {
@@ -109,6 +153,304 @@ pub fn build_square_matrix_from_selected_columns(
         }
     }
 }
+
+// information set with collision window
+/// An `r x N` parity-check matrix (`[h | I]` with r = n, N = 2n, or any `SdInstance`) under
+/// a random column permutation, with the first `r - l` permuted columns reduced to identity
+/// and the last `l` rows left over as the collision window:
+///
+/// ```text
+/// [ I_{r-l} | tops ] e = rhs_top
+/// [    0    | keys ] e = rhs_key
+/// ```
+///
+/// Non-pivot columns are indexed `0..N - r + l`: `0..l` are the window columns
+/// `perm[r - l..r]`, `l..N - r + l` are the information set `perm[r..N]`.
+pub struct SemiSystematicForm {
+    /// Rows r of the parity-check matrix (n for `[h | I]`).
+    pub rows: usize,
+    /// Columns N of the parity-check matrix (2n for `[h | I]`).
+    pub cols: usize,
+    pub l: usize,
+    pub perm: Vec<usize>,
+    /// Rows `0..rows - l` of each non-pivot column.
+    pub tops: Vec<HqcGf2>,
+    /// Rows `rows - l..rows` of each non-pivot column, bit `i` is row `rows - l + i`.
+    pub keys: Vec<u64>,
+    pub rhs_top: HqcGf2,
+    pub rhs_key: u64,
+    /// Rows of the accumulated row transform U (so `rhs = U·s`), kept only by `with_transform`.
+    pub transform: Vec<HqcGf2>,
+    /// r rows: the N permuted columns, then U (only `with_transform`), then s.
+    mat: BinaryMatrix,
+    /// Scratch for the transposed matrix, one row per column of `mat`.
+    mat_t: BinaryMatrix,
+    col_buf: HqcGf2,
+    tmp_words: Vec<u64>,
+}
+
+impl SemiSystematicForm {
+    /// The form of `[h | I]` for length-n h.
+    pub fn new(n: usize, l: usize) -> Self {
+        Self::with_shape(n, 2 * n, l)
+    }
+
+    /// The form of an `rows x cols` parity-check matrix.
+    pub fn with_shape(rows: usize, cols: usize, l: usize) -> Self {
+        assert!(l <= 64 && l <= rows, "window size l must be <= 64 and <= rows");
+        assert!(rows <= cols, "parity check must have rows <= cols");
+        let non_pivot = cols - rows + l;
+        Self {
+            rows,
+            cols,
+            l,
+            perm: (0..cols).collect(),
+            tops: (0..non_pivot).map(|_| HqcGf2::zero_with_len(rows - l)).collect(),
+            keys: vec![0u64; non_pivot],
+            rhs_top: HqcGf2::zero_with_len(rows - l),
+            rhs_key: 0,
+            transform: Vec::new(),
+            mat: BinaryMatrix::zero(rows, cols + 1),
+            mat_t: BinaryMatrix::zero(cols + 1, rows),
+            col_buf: HqcGf2::zero_with_len(rows),
+            tmp_words: vec![0u64; HqcGf2::word_len(rows)],
+        }
+    }
+
+    /// The form of `instance`'s parity-check matrix.
+    pub fn for_instance<I: SdInstance + ?Sized>(instance: &I, l: usize) -> Self {
+        Self::with_shape(instance.redundancy(), instance.code_len(), l)
+    }
+
+    /// Like `new`, but also keeps the row transform U so that other syndromes can be reduced
+    /// with `reduce_syndrome` after the elimination.
+    pub fn with_transform(n: usize, l: usize) -> Self {
+        let mut form = Self::new(n, l);
+        form.mat = BinaryMatrix::zero(n, 3 * n + 1);
+        form.mat_t = BinaryMatrix::zero(3 * n + 1, n);
+        form.transform = (0..n).map(|_| HqcGf2::zero_with_len(n)).collect();
+        form
+    }
+
+    #[inline]
+    fn tracks_transform(&self) -> bool {
+        !self.transform.is_empty()
+    }
+
+    /// Number of non-pivot columns (window plus information set).
+    #[inline]
+    pub fn non_pivot_len(&self) -> usize {
+        self.cols - self.rows + self.l
+    }
+
+    /// Size of the information set (n for `[h | I]`).
+    #[inline]
+    pub fn info_len(&self) -> usize {
+        self.cols - self.rows
+    }
+
+    /// Draw a fresh permutation and reduce `[h | I]`. false if the pivot columns are singular.
+    pub fn reduce<R: Rng>(&mut self, rng: &mut R, h: &HqcGf2, s: &HqcGf2) -> bool {
+        self.reduce_columns(rng, s, |c, out, tmp| hqc_column_into(h, c, out, tmp))
+    }
+
+    /// `reduce` for the parity-check matrix and syndrome of `instance`.
+    pub fn reduce_instance<R: Rng, I: SdInstance + ?Sized>(&mut self, rng: &mut R, instance: &I) -> bool {
+        self.reduce_columns(rng, instance.syndrome(), |c, out, tmp| {
+            instance.column_into(c, out, tmp)
+        })
+    }
+
+    fn reduce_columns<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        s: &HqcGf2,
+        mut column_into: impl FnMut(usize, &mut HqcGf2, &mut Vec<u64>),
+    ) -> bool {
+        let (rows, cols) = (self.rows, self.cols);
+        sample_cols(rng, &mut self.perm, rows);
+        self.perm[rows..].shuffle(rng);
+        for (k, &col) in self.perm.iter().enumerate() {
+            column_into(col, &mut self.col_buf, &mut self.tmp_words);
+            self.mat_t.set_row(k, &self.col_buf);
+        }
+        if self.tracks_transform() {
+            for r in 0..rows {
+                let row = cols + r;
+                self.mat_t.row_mut(row).fill(0);
+                self.mat_t.set(row, r);
+            }
+        }
+        let rhs_col = self.mat.cols() - 1;
+        self.mat_t.set_row(rhs_col, s);
+        self.mat_t.transpose_into(&mut self.mat);
+        if !self.mat.systematize(rows - self.l) {
+            return false;
+        }
+        self.extract();
+        true
+    }
+
+    fn extract(&mut self) {
+        let top = self.rows - self.l;
+        self.mat.transpose_into(&mut self.mat_t);
+        for (j, t) in self.tops.iter_mut().enumerate() {
+            *t = self.mat_t.row_bits(top + j, 0, top);
+            self.keys[j] = self.mat_t.row_bits_u64(top + j, top, self.l);
+        }
+        let rhs_col = self.mat.cols() - 1;
+        self.rhs_top = self.mat_t.row_bits(rhs_col, 0, top);
+        self.rhs_key = self.mat_t.row_bits_u64(rhs_col, top, self.l);
+        for (r, u) in self.transform.iter_mut().enumerate() {
+            *u = self.mat.row_bits(r, self.cols, self.rows);
+        }
+    }
+
+    /// `U·v` split into its pivot part and its window key. Needs `with_transform`.
+    pub fn reduce_syndrome(&self, v: &HqcGf2) -> (HqcGf2, u64) {
+        assert!(self.tracks_transform(), "reduce_syndrome needs with_transform");
+        let top = self.rows - self.l;
+        let mut rhs_top = HqcGf2::zero_with_len(top);
+        let mut rhs_key = 0u64;
+        for (r, u) in self.transform.iter().enumerate() {
+            let parity = u
+                .words
+                .iter()
+                .zip(&v.words)
+                .fold(0u32, |acc, (a, b)| acc ^ (a & b).count_ones())
+                & 1;
+            if parity == 1 {
+                if r < top {
+                    rhs_top.set(r);
+                } else {
+                    rhs_key |= 1u64 << (r - top);
+                }
+            }
+        }
+        (rhs_top, rhs_key)
+    }
+
+    /// Window keys of `U·(X^i·s)` for every rotation `i` in `0..n`. Needs `with_transform`.
+    ///
+    /// Row r of `U·(X^i·s)` is `sum_k U[r][k]·s[k - i]`, i.e. coefficient i of the cyclic
+    /// product of `U[r]` with the reversal of s.
+    pub fn rotated_rhs_keys(&self, s: &HqcGf2) -> Vec<u64> {
+        assert!(self.tracks_transform(), "rotated_rhs_keys needs with_transform");
+        let n = self.rows;
+        let top = n - self.l;
+        let mut s_rev = HqcGf2::zero_with_len(n);
+        for k in s.ones_indices() {
+            s_rev.set((n - k) % n);
+        }
+        let mut keys = vec![0u64; n];
+        for r in top..n {
+            let corr = self.transform[r].mul_bitpacked(&s_rev);
+            for i in corr.ones_indices() {
+                keys[i] |= 1u64 << (r - top);
+            }
+        }
+        keys
+    }
+
+    /// XOR of the window keys of the given non-pivot columns.
+    #[inline]
+    pub fn key_of(&self, cols: &[usize]) -> u64 {
+        cols.iter().fold(0u64, |acc, &c| acc ^ self.keys[c])
+    }
+
+    /// Complete a candidate whose non-pivot error positions are `cols`: the pivot part of the
+    /// error is forced to `rhs_top + sum(tops[c])`. Some(y) if the full error splits as
+    /// `wt(y) = wt(x) = w` and satisfies `x + h·y = s`.
+    pub fn recover(&self, cols: &[usize], w: usize, h: &HqcGf2, s: &HqcGf2) -> Option<HqcGf2> {
+        self.recover_with(cols, &self.rhs_top, self.rhs_key, w, h, s)
+    }
+
+    /// `recover` against another syndrome s, given its reduction `(rhs_top, rhs_key)`.
+    pub fn recover_with(
+        &self,
+        cols: &[usize],
+        rhs_top: &HqcGf2,
+        rhs_key: u64,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+    ) -> Option<HqcGf2> {
+        let n = self.rows;
+        let mut y = HqcGf2::zero_with_len(n);
+        let mut x = HqcGf2::zero_with_len(n);
+        for orig_col in self.error_support(cols, rhs_top, rhs_key, 2 * w)? {
+            if orig_col < n {
+                y.toggle(orig_col);
+            } else {
+                x.toggle(orig_col - n);
+            }
+        }
+        check_pair(w, h, s, &x, &y).ok()?;
+        Some(y)
+    }
+
+    /// `recover` for `instance`: Some(e) if the completed error is a solution of it.
+    pub fn recover_error<I: SdInstance + ?Sized>(&self, cols: &[usize], instance: &I) -> Option<HqcGf2> {
+        let support = self.error_support(cols, &self.rhs_top, self.rhs_key, instance.weight())?;
+        let e = HqcGf2::from_indices(self.cols, &support);
+        instance.is_solution(&e).then_some(e)
+    }
+
+    /// Original column indices of the error with non-pivot positions `cols`, if its window
+    /// key matches and its total weight is `weight`.
+    fn error_support(&self, cols: &[usize], rhs_top: &HqcGf2, rhs_key: u64, weight: usize) -> Option<Vec<usize>> {
+        if self.key_of(cols) != rhs_key {
+            return None;
+        }
+        let mut e_top = rhs_top.clone();
+        for &c in cols {
+            e_top.xor_in_place(&self.tops[c]);
+        }
+        if e_top.weight() as usize + cols.len() != weight {
+            return None;
+        }
+        let top = self.rows - self.l;
+        let positions = e_top
+            .ones_indices()
+            .into_iter()
+            .chain(cols.iter().map(|&c| top + c));
+        Some(positions.map(|k| self.perm[k]).collect())
+    }
+}
+/// Row k of `mat_t` becomes column `cols[k]` of `[h | I]`, i.e. `mat_t` is the transpose of
+/// the selected columns. Rows past `cols.len()` are left untouched.
+pub fn load_selected_columns_transposed(
+    n: usize,
+    h: &HqcGf2,
+    cols: &[usize],
+    mat_t: &mut BinaryMatrix,
+    col_buf: &mut HqcGf2,
+    tmp_words: &mut Vec<u64>,
+) {
+    debug_assert!(mat_t.rows() >= cols.len());
+    debug_assert_eq!(mat_t.cols(), n);
+    for (k, &col) in cols.iter().enumerate() {
+        hqc_column_into(h, col, col_buf, tmp_words);
+        mat_t.set_row(k, col_buf);
+    }
+}
+
+/// `load_selected_columns_transposed` for the parity-check matrix of `instance`.
+pub fn load_instance_columns_transposed<I: SdInstance + ?Sized>(
+    instance: &I,
+    cols: &[usize],
+    mat_t: &mut BinaryMatrix,
+    col_buf: &mut HqcGf2,
+    tmp_words: &mut Vec<u64>,
+) {
+    debug_assert!(mat_t.rows() >= cols.len());
+    debug_assert_eq!(mat_t.cols(), instance.redundancy());
+    for (k, &col) in cols.iter().enumerate() {
+        instance.column_into(col, col_buf, tmp_words);
+        mat_t.set_row(k, col_buf);
+    }
+}
+
 // stern
 /// calculates key = (h*y)_J
 pub fn h_mul_y_on_window(
@@ -193,4 +535,220 @@ pub fn for_each_combination_cf(
     }
 
     ControlFlow::Continue(())
-}
\ No newline at end of file
+}
+
+/// Step `comb` (sorted, `< n`) to the next k-combination in revolving-door order (Knuth,
+/// TAOCP 7.2.1.3, Algorithm R), starting from `0..k`. Consecutive combinations differ in one
+/// index: returns `(left, entered)`, or None (leaving `comb` as is) after the last one.
+pub fn revolving_door_next(comb: &mut [usize], n: usize) -> Option<(usize, usize)> {
+    let t = comb.len();
+    if t == 0 || t >= n {
+        return None;
+    }
+    // R3: the easy case moves c_1.
+    if t % 2 == 1 {
+        let next = if t > 1 { comb[1] } else { n };
+        if comb[0] + 1 < next {
+            comb[0] += 1;
+            return Some((comb[0] - 1, comb[0]));
+        }
+    } else if comb[0] > 0 {
+        comb[0] -= 1;
+        return Some((comb[0] + 1, comb[0]));
+    }
+    // R4/R5 alternate on c_j (1-based, c_{t+1} = n), starting with R4 for odd t.
+    let mut decrease = t % 2 == 1;
+    for j in 2..=t {
+        if decrease {
+            // c_j = c_{j-1} + 1
+            if comb[j - 1] >= j {
+                let left = comb[j - 1];
+                comb[j - 1] = comb[j - 2];
+                comb[j - 2] = j - 2;
+                return Some((left, j - 2));
+            }
+        } else {
+            // c_{j-1} = j - 2
+            let next = if j < t { comb[j] } else { n };
+            if comb[j - 1] + 1 < next {
+                comb[j - 2] = comb[j - 1];
+                comb[j - 1] += 1;
+                return Some((j - 2, comb[j - 1]));
+            }
+        }
+        decrease = !decrease;
+    }
+    None
+}
+
+/// `for_each_combination_cf` in revolving-door order. `f` also gets the index that left and
+/// the one that entered since the previous combination (None for the first), so that sums
+/// over the support can be updated with two XORs instead of recomputed.
+pub fn for_each_combination_revolving_cf(
+    n: usize,
+    k: usize,
+    mut f: impl FnMut(&[usize], Option<(usize, usize)>) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    if k > n {
+        return ControlFlow::Continue(());
+    }
+    let mut comb: Vec<usize> = (0..k).collect();
+    let mut swap = None;
+    loop {
+        if let ControlFlow::Break(()) = f(&comb, swap) {
+            return ControlFlow::Break(());
+        }
+        swap = revolving_door_next(&mut comb, n);
+        if swap.is_none() {
+            return ControlFlow::Continue(());
+        }
+    }
+}
+
+/// Window keys `(h·X^t)_J` of every t in `0..n`: `h_mul_y_on_window` is the XOR of the keys
+/// of the support of y, so it can follow a revolving-door enumeration one index at a time.
+pub struct WindowColumnKeys {
+    key_words: usize,
+    keys: Vec<u64>,
+}
+
+impl WindowColumnKeys {
+    pub fn new(n: usize, h: &HqcGf2, window: &[usize]) -> Self {
+        let key_words = window.len().div_ceil(64);
+        let mut keys = vec![0u64; n * key_words];
+        for (t, key) in keys.chunks_exact_mut(key_words.max(1)).enumerate() {
+            for (i, &pos) in window.iter().enumerate() {
+                if h.get((pos + n - t) % n) {
+                    key[i / 64] |= 1u64 << (i % 64);
+                }
+            }
+        }
+        Self { key_words, keys }
+    }
+
+    /// Words per key.
+    pub fn key_words(&self) -> usize {
+        self.key_words
+    }
+
+    /// `key ^= (h·X^t)_J`.
+    #[inline]
+    pub fn toggle(&self, t: usize, key: &mut [u64]) {
+        let col = &self.keys[t * self.key_words..(t + 1) * self.key_words];
+        for (k, c) in key.iter_mut().zip(col) {
+            *k ^= c;
+        }
+    }
+
+    /// `key = (h·y)_J` for the support of y.
+    pub fn key_of(&self, support: impl IntoIterator<Item = usize>, key: &mut [u64]) {
+        key.fill(0);
+        for t in support {
+            self.toggle(t, key);
+        }
+    }
+}
+// merge lists
+/// One element of a merge list: sorted non-pivot columns and the XOR of their window keys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ListEntry {
+    pub key: u64,
+    pub cols: Vec<usize>,
+}
+
+/// Mask selecting the low `bits` bits of a window key.
+#[inline]
+pub fn low_mask(bits: usize) -> u64 {
+    if bits >= 64 { !0u64 } else { (1u64 << bits) - 1 }
+}
+
+/// All `weight`-subsets of the non-pivot columns `offset..offset + len`.
+pub fn list_of_combinations(
+    form: &SemiSystematicForm,
+    offset: usize,
+    len: usize,
+    weight: usize,
+) -> Vec<ListEntry> {
+    let mut out = Vec::new();
+    let _ = for_each_combination_cf(len, weight, |support| {
+        let cols: Vec<usize> = support.iter().map(|&i| offset + i).collect();
+        out.push(ListEntry { key: form.key_of(&cols), cols });
+        ControlFlow::Continue(())
+    });
+    out
+}
+
+/// Symmetric difference of two sorted column lists (the support of the sum).
+pub fn xor_supports(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => {
+                out.push(a[i]);
+                i += 1;
+            }
+            std::cmp::Ordering::Greater => {
+                out.push(b[j]);
+                j += 1;
+            }
+            std::cmp::Ordering::Equal => {
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    out.extend_from_slice(&a[i..]);
+    out.extend_from_slice(&b[j..]);
+    out
+}
+
+/// Sort-and-match join: calls `f(a, b)` for every pair with `(a.key ^ b.key) & mask == target & mask`.
+/// Both lists are sorted in place.
+pub fn join_on_mask(
+    left: &mut [ListEntry],
+    right: &mut [ListEntry],
+    mask: u64,
+    target: u64,
+    mut f: impl FnMut(&ListEntry, &ListEntry) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    join_buckets_on_mask(left, right, mask, target, |xs, ys| {
+        for x in xs {
+            for y in ys {
+                f(x, y)?;
+            }
+        }
+        ControlFlow::Continue(())
+    })
+}
+
+/// `join_on_mask` one bucket at a time: calls `f(xs, ys)` for every maximal run of left
+/// entries and right entries whose masked keys match.
+pub fn join_buckets_on_mask(
+    left: &mut [ListEntry],
+    right: &mut [ListEntry],
+    mask: u64,
+    target: u64,
+    mut f: impl FnMut(&[ListEntry], &[ListEntry]) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let target = target & mask;
+    left.sort_unstable_by_key(|e| e.key & mask);
+    right.sort_unstable_by_key(|e| (e.key ^ target) & mask);
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        let a = left[i].key & mask;
+        let b = (right[j].key ^ target) & mask;
+        if a < b {
+            i += 1;
+        } else if a > b {
+            j += 1;
+        } else {
+            let i_end = i + left[i..].iter().take_while(|e| e.key & mask == a).count();
+            let j_end = j + right[j..].iter().take_while(|e| (e.key ^ target) & mask == b).count();
+            f(&left[i..i_end], &right[j..j_end])?;
+            i = i_end;
+            j = j_end;
+        }
+    }
+    ControlFlow::Continue(())
+}
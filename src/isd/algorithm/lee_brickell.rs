@@ -0,0 +1,126 @@
+use super::*;
+use crate::hqc::hqcgf2::HqcGf2;
+use std::ops::ControlFlow;
+
+/// Lee–Brickell variant of Prange: after each elimination, also try every combination of
+/// up to p information-set columns and check whether the residual has the right weight.
+/// `p = 0` is Prange.
+pub struct LeeBrickell {
+    pub p: usize,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+impl LeeBrickell {
+    pub fn new(p: usize, max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self { p, max_iters, seed }
+    }
+}
+
+impl Default for LeeBrickell {
+    fn default() -> Self {
+        Self {
+            p: 2,
+            max_iters: Some(2000000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for LeeBrickell {
+    fn name(&self) -> &'static str {
+        "Lee-Brickell"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for LeeBrickell {
+    fn config(&self) -> String {
+        format!("p={}", self.p)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        if self.p > 2 * w || self.p > n {
+            return Err(AttackError::InvalidParameter(
+                "parameter p must be <= 2w and <= n".to_string(),
+            ));
+        }
+
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let max_iters = self.max_iters.unwrap_or(u64::MAX);
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        let mut form = SemiSystematicForm::new(n, 0);
+        cursor.restore_perm(&mut form.perm)?;
+
+        loop {
+            cursor.mark(&rng, &form.perm);
+            if stats.iterations >= max_iters {
+                break;
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            if !stats.record_elimination(form.reduce(&mut rng, h, s)) {
+                continue;
+            }
+            for k in 0..=self.p {
+                let mut answer = None;
+                // Pivot part of the error, kept up to date as the subset changes.
+                let mut e_top = form.rhs_top.clone();
+                let _ = for_each_combination_revolving_cf(n, k, |cols, swap| {
+                    match swap {
+                        None => cols.iter().for_each(|&c| e_top.xor_in_place(&form.tops[c])),
+                        Some((left, entered)) => {
+                            e_top.xor_in_place(&form.tops[left]);
+                            e_top.xor_in_place(&form.tops[entered]);
+                        }
+                    }
+                    stats.candidate_checks += 1;
+                    if e_top.weight() as usize + k != 2 * w {
+                        return ControlFlow::Continue(());
+                    }
+                    answer = form.recover(cols, w, h, s);
+                    match answer {
+                        Some(_) => ControlFlow::Break(()),
+                        None => ControlFlow::Continue(()),
+                    }
+                });
+                if answer.is_some() {
+                    return Ok(answer);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
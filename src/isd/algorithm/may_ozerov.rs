@@ -0,0 +1,205 @@
+use super::*;
+use super::nn::NnBackend;
+use crate::isd::estimator::log2_binomial;
+use crate::gf::gf2::Gf2Construct;
+use crate::hqc::hqcgf2::HqcGf2;
+use crate::isd::instance::{QcDoubleCirculant, SdAttack, SdInstance};
+use rand::Rng;
+use std::ops::ControlFlow;
+
+/// May–Ozerov ISD on `[h | I]`: Dumer with the exact window collision replaced by a
+/// nearest-neighbour search.
+///
+/// Each iteration permutes the 2n columns and reduces `n - l` of them to identity. p-subsets of
+/// both halves of the `k + l` non-pivot columns are listed and first matched exactly on the
+/// l window bits (`l = 0` skips this). Within each matching bucket, a left entry contributes
+/// `a = rhs_top + sum(tops)` and a right entry `b = sum(tops)`, and the pairs with
+/// `wt(a + b) = 2w - 2p` are exactly those whose pivot part completes the error. Finding them
+/// is a nearest-neighbour problem in dimension `n - l`, solved by `nn`.
+pub struct MayOzerov {
+    pub p: usize,
+    pub l: usize,
+    pub nn: NnBackend,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+impl MayOzerov {
+    pub fn new(p: usize, l: usize, nn: NnBackend, max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self { p, l, nn, max_iters, seed }
+    }
+}
+
+impl Default for MayOzerov {
+    fn default() -> Self {
+        Self {
+            p: 1,
+            l: 0,
+            nn: NnBackend::BitSampling { bits: 16, repetitions: 4 },
+            max_iters: Some(200000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for MayOzerov {
+    fn name(&self) -> &'static str {
+        "May-Ozerov"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for MayOzerov {
+    fn config(&self) -> String {
+        format!("p={} l={} nn={:?}", self.p, self.l, self.nn)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let instance = QcDoubleCirculant::new(h.clone(), s.clone(), w)?;
+        let e = self.run(&instance, budget, cursor, stats)?;
+        Ok(e.map(|e| instance.y_of(&e)))
+    }
+}
+
+impl SdAttack for MayOzerov {
+    fn solve_instance<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        self.run(instance, budget, &mut Cursor::default(), stats)
+    }
+}
+
+impl MayOzerov {
+    fn run<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        cursor: &mut Cursor,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        let rows = instance.redundancy();
+        if self.l > 64 || self.l > rows {
+            return Err(AttackError::InvalidParameter(
+                "parameter l must be <= 64 and <= n - k".to_string(),
+            ));
+        }
+        let len = instance.code_len() - rows + self.l;
+        let half = len / 2;
+        if 2 * self.p > instance.weight() || self.p > half {
+            return Err(AttackError::InvalidParameter(
+                "parameter p must be <= weight / 2 and <= (k + l) / 2".to_string(),
+            ));
+        }
+        self.nn.validate(rows - self.l).map_err(AttackError::InvalidParameter)?;
+        let max_iters = self.max_iters.unwrap_or(u64::MAX);
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        let mut form = SemiSystematicForm::for_instance(instance, self.l);
+        cursor.restore_perm(&mut form.perm)?;
+        let entries = log2_binomial(half, self.p).exp2() + log2_binomial(len - half, self.p).exp2();
+        if !stats.table_fits(budget, entries, table_entry_bytes(self.p)) {
+            return Ok(None);
+        }
+        let dist = instance.weight() - 2 * self.p;
+
+        loop {
+            cursor.mark(&rng, &form.perm);
+            if stats.iterations >= max_iters {
+                break;
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            if !stats.record_elimination(form.reduce_instance(&mut rng, instance)) {
+                continue;
+            }
+            let found = may_ozerov_merge_with(&form, self.p, dist, self.nn, &mut rng, stats, |cols| {
+                form.recover_error(cols, instance)
+            });
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Dumer's lists, joined exactly on the window and then by `nn` on the pivot rows: every pair
+/// at distance `dist` is handed to `recover` with its non-pivot columns.
+pub fn may_ozerov_merge_with<T, R: Rng>(
+    form: &SemiSystematicForm,
+    p: usize,
+    dist: usize,
+    nn: NnBackend,
+    rng: &mut R,
+    stats: &mut AttackStats,
+    mut recover: impl FnMut(&[usize]) -> Option<T>,
+) -> Option<T> {
+    let len = form.non_pivot_len();
+    let n1 = len / 2;
+    let mut left = list_of_combinations(form, 0, n1, p);
+    let mut right = list_of_combinations(form, n1, len - n1, p);
+    stats.record_table(left.len() + right.len());
+
+    let pivot_part = |start: &HqcGf2, cols: &[usize]| {
+        let mut v = start.clone();
+        for &c in cols {
+            v.xor_in_place(&form.tops[c]);
+        }
+        v
+    };
+    let zero = HqcGf2::zero_with_len(form.rhs_top.n);
+    let mut answer = None;
+    let mut cols = Vec::with_capacity(2 * p);
+    let _ = join_buckets_on_mask(&mut left, &mut right, low_mask(form.l), form.rhs_key, |xs, ys| {
+        let a: Vec<HqcGf2> = xs.iter().map(|e| pivot_part(&form.rhs_top, &e.cols)).collect();
+        let b: Vec<HqcGf2> = ys.iter().map(|e| pivot_part(&zero, &e.cols)).collect();
+        nn.for_each_close_pair(&a, &b, dist, rng, stats, |i, j| {
+            cols.clear();
+            cols.extend_from_slice(&xs[i].cols);
+            cols.extend_from_slice(&ys[j].cols);
+            match recover(&cols) {
+                Some(found) => {
+                    answer = Some(found);
+                    ControlFlow::Break(())
+                }
+                None => ControlFlow::Continue(()),
+            }
+        })
+    });
+    answer
+}
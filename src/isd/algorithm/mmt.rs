@@ -0,0 +1,78 @@
+use super::*;
+use super::bjmm::run_representation_attack;
+use crate::hqc::hqcgf2::HqcGf2;
+
+/// May–Meurer–Thomae ISD on `[h | I]`.
+///
+/// Same two-level merge tree as a depth-2 `Bjmm`, but the weight-p part of the error is only
+/// split as a disjoint sum of two weight-p/2 vectors: no overlapping ones (ε = 0).
+pub struct Mmt {
+    pub p: usize,
+    pub l: usize,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+impl Mmt {
+    pub fn new(p: usize, l: usize, max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self { p, l, max_iters, seed }
+    }
+}
+
+impl Default for Mmt {
+    fn default() -> Self {
+        Self {
+            p: 2,
+            l: 16,
+            max_iters: Some(200000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for Mmt {
+    fn name(&self) -> &'static str {
+        "MMT"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for Mmt {
+    fn config(&self) -> String {
+        format!("p={} l={}", self.p, self.l)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        checkpoint.claim(self, n, w)?;
+        run_representation_attack(
+            n, w, h, s, self.p, self.l, &[0], self.max_iters, self.seed, budget, checkpoint,
+        )
+    }
+}
@@ -2,7 +2,20 @@ pub mod brute_force;
 pub mod prange;
 pub mod helper;
 pub mod stern;
+pub mod birthday;
+pub mod lee_brickell;
+pub mod bjmm;
+pub mod mmt;
+pub mod ball_collision;
+pub mod doom;
+pub mod dumer;
+pub mod may_ozerov;
+pub mod nn;
+pub mod rlpn;
+pub mod sieve;
 
 use helper::*;
-use super::attack::Attack;
+use super::attack::{Attack, AttackStats};
+use super::budget::{table_entry_bytes, AttackBudget};
+use super::checkpoint::{solve_fresh, Checkpoint, Cursor, Resumable};
 use super::error::AttackError;
\ No newline at end of file
@@ -0,0 +1,125 @@
+//! Nearest-neighbour search between two lists of binary vectors: find the pairs `(a, b)` with
+//! `wt(a + b) = d`.
+//!
+//! Backends may miss pairs but never report a pair at another distance. Every distance they
+//! compute is counted as a candidate check, so `AttackStats::candidate_checks` measures how
+//! much of the quadratic pair space a backend actually touches.
+
+use super::AttackStats;
+use crate::hqc::hqcgf2::HqcGf2;
+use rand::Rng;
+use rand::seq::index::sample;
+use std::ops::ControlFlow;
+
+/// How the nearest-neighbour step of `MayOzerov` searches for close pairs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NnBackend {
+    /// Compare every pair. Finds all of them at quadratic cost.
+    Exhaustive,
+    /// Indyk–Motwani bit sampling, the building block of May–Ozerov: `repetitions` rounds,
+    /// each bucketing both lists on `bits` random coordinates and comparing only within a
+    /// bucket. A pair at distance d in dimension D survives one round with probability
+    /// `C(D - d, bits) / C(D, bits)`, which stays large when d is small compared to D, as
+    /// it is for HQC's `w ≈ √n`.
+    BitSampling { bits: usize, repetitions: usize },
+}
+
+impl NnBackend {
+    /// Check the backend's parameters against vectors of length `dim`.
+    pub fn validate(&self, dim: usize) -> Result<(), String> {
+        match *self {
+            NnBackend::Exhaustive => Ok(()),
+            NnBackend::BitSampling { bits, repetitions } => {
+                if bits > 64 || bits > dim {
+                    return Err("parameter bits must be <= 64 and <= n - l".to_string());
+                }
+                if repetitions == 0 {
+                    return Err("parameter repetitions must be > 0".to_string());
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Call `f(i, j)` for pairs with `wt(left[i] + right[j]) = dist`.
+    pub fn for_each_close_pair<R: Rng>(
+        &self,
+        left: &[HqcGf2],
+        right: &[HqcGf2],
+        dist: usize,
+        rng: &mut R,
+        stats: &mut AttackStats,
+        mut f: impl FnMut(usize, usize) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let mut check = |i: usize, j: usize, stats: &mut AttackStats| {
+            stats.candidate_checks += 1;
+            if distance(&left[i], &right[j]) == dist {
+                f(i, j)
+            } else {
+                ControlFlow::Continue(())
+            }
+        };
+        match *self {
+            NnBackend::Exhaustive => {
+                for i in 0..left.len() {
+                    for j in 0..right.len() {
+                        check(i, j, stats)?;
+                    }
+                }
+            }
+            NnBackend::BitSampling { bits, repetitions } => {
+                let dim = left.first().or(right.first()).map_or(0, |v| v.n);
+                for _ in 0..repetitions {
+                    let coords = sample(rng, dim, bits.min(dim)).into_vec();
+                    let mut lk = sampled_keys(left, &coords);
+                    let mut rk = sampled_keys(right, &coords);
+                    lk.sort_unstable();
+                    rk.sort_unstable();
+                    let (mut a, mut b) = (0, 0);
+                    while a < lk.len() && b < rk.len() {
+                        if lk[a].0 < rk[b].0 {
+                            a += 1;
+                        } else if lk[a].0 > rk[b].0 {
+                            b += 1;
+                        } else {
+                            let key = lk[a].0;
+                            let a_end = a + lk[a..].iter().take_while(|e| e.0 == key).count();
+                            let b_end = b + rk[b..].iter().take_while(|e| e.0 == key).count();
+                            for &(_, i) in &lk[a..a_end] {
+                                for &(_, j) in &rk[b..b_end] {
+                                    check(i, j, stats)?;
+                                }
+                            }
+                            a = a_end;
+                            b = b_end;
+                        }
+                    }
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Hamming distance of two vectors of the same length.
+pub fn distance(a: &HqcGf2, b: &HqcGf2) -> usize {
+    a.words
+        .iter()
+        .zip(&b.words)
+        .map(|(x, y)| (x ^ y).count_ones() as usize)
+        .sum()
+}
+
+/// `(key, index)` of every vector, bit t of the key being coordinate `coords[t]`.
+fn sampled_keys(vs: &[HqcGf2], coords: &[usize]) -> Vec<(u64, usize)> {
+    vs.iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let key = coords
+                .iter()
+                .enumerate()
+                .fold(0u64, |k, (t, &c)| k | (v.get(c) as u64) << t);
+            (key, i)
+        })
+        .collect()
+}
@@ -1,16 +1,26 @@
 use super::*;
+use crate::gf::binary_matrix::BinaryMatrix;
 use crate::gf::gf2::Gf2Construct;
 use crate::hqc::hqcgf2::HqcGf2;
-use rand::{RngCore, SeedableRng};
-use rand::rngs::{OsRng, StdRng};
+use crate::isd::instance::{QcDoubleCirculant, SdAttack, SdInstance};
+use rand::Rng;
+use rand_chacha::ChaCha12Rng;
 
 pub struct Prange {
     pub max_iters: Option<u64>,
     pub seed: Option<u64>,
+    /// Canteaut–Chabaud mode: instead of a fresh elimination, each iteration swaps this many
+    /// columns between the selected set and its complement, one pivot per swap.
+    pub step: Option<usize>,
 }
 impl Prange {
     pub fn new(max_iters: Option<u64>, seed: Option<u64>) -> Self {
-        Self { max_iters, seed }
+        Self { max_iters, seed, step: None }
+    }
+    /// Switch to incremental updates swapping `c` columns per iteration.
+    pub fn with_step(mut self, c: usize) -> Self {
+        self.step = Some(c);
+        self
     }
 }
 impl Default for Prange {
@@ -18,6 +28,7 @@ impl Default for Prange {
         Self {
             max_iters: Some(2000000),
             seed: None,
+            step: None,
         }
     }
 }
@@ -26,12 +37,32 @@ impl Attack for Prange {
         "Prange"
     }
 
-    fn solve(
+    fn solve_within(
         &self,
         n: usize,
         w: usize,
         h: &HqcGf2,
         s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for Prange {
+    fn config(&self) -> String {
+        format!("step={:?}", self.step)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
     ) -> Result<Option<HqcGf2>, AttackError> {
         if h.n != n || s.n != n {
             return Err(AttackError::InvalidParameter(
@@ -42,58 +73,200 @@ impl Attack for Prange {
             return Ok(None);
         }
 
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let instance = QcDoubleCirculant::new(h.clone(), s.clone(), w)?;
+        let e = self.run(&instance, budget, cursor, stats)?;
+        Ok(e.map(|e| instance.y_of(&e)))
+    }
+}
+
+impl SdAttack for Prange {
+    fn solve_instance<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        self.run(instance, budget, &mut Cursor::default(), stats)
+    }
+}
+
+impl Prange {
+    fn run<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        cursor: &mut Cursor,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if self.step == Some(0) {
+            return Err(AttackError::InvalidParameter(
+                "parameter step must be > 0".to_string(),
+            ));
+        }
+        let rows = instance.redundancy();
+        let cols = instance.code_len();
+        if rows > cols {
+            return Err(AttackError::InvalidParameter(
+                "parity check must have n - k <= n".to_string(),
+            ));
+        }
         let max_iters = self.max_iters.unwrap_or(u64::MAX);
-        let seed = match self.seed {
-            Some(v) => v,
-            None => OsRng.next_u64(),
-        };
-        let mut rng = StdRng::seed_from_u64(seed);
-        let mut mat_rows: Vec<HqcGf2> = (0..n).map(|_| HqcGf2::zero_with_len(n)).collect();
-        let mut rhs = HqcGf2::zero_with_len(n);
-        let mut col_buf = HqcGf2::zero_with_len(n);
-        let mut tmp_words: Vec<u64> = vec![0u64; HqcGf2::word_len(n)];
-        let mut perm: Vec<usize> = (0..2 * n).collect();
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        if let Some(c) = self.step {
+            return solve_incremental(instance, c, max_iters, &mut rng, budget, cursor, stats);
+        }
+        // rows x (rows + 1): the selected columns, then s
+        let mut mat = BinaryMatrix::zero(rows, rows + 1);
+        let mut mat_t = BinaryMatrix::zero(rows + 1, rows);
+        let mut col_buf = HqcGf2::zero_with_len(rows);
+        let mut tmp_words: Vec<u64> = vec![0u64; HqcGf2::word_len(rows)];
+        let mut perm: Vec<usize> = (0..cols).collect();
+        cursor.restore_perm(&mut perm)?;
 
-        for _ in 0..max_iters {
-            sample_cols(&mut rng, &mut perm, n);
-            let cols = &perm[..n];
-            build_square_matrix_from_selected_columns(
-                n,
-                h,
-                cols,
-                &mut mat_rows,
-                &mut col_buf,
-                &mut tmp_words,
-            );
-            rhs.copy_from_same_len(s);
-            if !gaussian_elimination_for_isd_instance(&mut mat_rows, &mut rhs) {
-                continue;
+        loop {
+            cursor.mark(&rng, &perm);
+            if stats.iterations >= max_iters {
+                break;
             }
-            let mut y = HqcGf2::zero_with_len(n);
-            let mut x = HqcGf2::zero_with_len(n);
-
-            for (k, &orig_col) in cols.iter().enumerate() {
-                if rhs.get(k) {
-                    if orig_col < n {
-                        y.set(orig_col);
-                    } else {
-                        x.set(orig_col - n);
-                    }
-                }
+            if stats.should_stop(budget) {
+                return Ok(None);
             }
-            if y.weight() as usize != w || x.weight() as usize != w {
+            stats.iterations += 1;
+            sample_cols(&mut rng, &mut perm, rows);
+            let selected = &perm[..rows];
+            load_instance_columns_transposed(instance, selected, &mut mat_t, &mut col_buf, &mut tmp_words);
+            mat_t.set_row(rows, instance.syndrome());
+            mat_t.transpose_into(&mut mat);
+            if !stats.record_elimination(mat.systematize(rows)) {
                 continue;
             }
-            let hy = h.mul_bitpacked(&y);
-            let mut lhs = x.clone();
-            lhs.xor_in_place(&hy);
-            if lhs != *s {
-                continue;
+            let rhs = mat.column(rows);
+            stats.candidate_checks += 1;
+            if let Some(e) = prange_candidate(instance, selected, &rhs) {
+                return Ok(Some(e));
             }
-
-            return Ok(Some(y));
         }
 
         Ok(None)
     }
 }
+
+/// Error supported on the selected columns `cols`, with `rhs` its value on them.
+/// Some(e) if it is a solution of `instance`.
+fn prange_candidate<I: SdInstance + ?Sized>(
+    instance: &I,
+    cols: &[usize],
+    rhs: &HqcGf2,
+) -> Option<HqcGf2> {
+    if rhs.weight() as usize != instance.weight() {
+        return None;
+    }
+    let support: Vec<usize> = rhs.ones_indices().into_iter().map(|k| cols[k]).collect();
+    let e = HqcGf2::from_indices(instance.code_len(), &support);
+    instance.is_solution(&e).then_some(e)
+}
+
+/// Canteaut–Chabaud Prange: keep H in systematic form over the whole (n - k) x n matrix
+/// and move between selected sets by single pivots instead of fresh eliminations.
+///
+/// The checkpoint position is `[0]` while a fresh elimination is pending, `[1]` when the
+/// saved permutation has an invertible first half whose candidate is still to be checked and
+/// `[2]` when it was checked and the next pivots are due; resuming from `[1]` or `[2]`
+/// re-eliminates on the saved permutation directly. Failed eliminations count as iterations,
+/// and after `rows * (cols - rows)` failed pivot attempts the search falls back to a fresh
+/// elimination.
+#[allow(clippy::too_many_arguments)]
+fn solve_incremental<I: SdInstance + ?Sized>(
+    instance: &I,
+    c: usize,
+    max_iters: u64,
+    rng: &mut ChaCha12Rng,
+    budget: &AttackBudget,
+    cursor: &mut Cursor,
+    stats: &mut AttackStats,
+) -> Result<Option<HqcGf2>, AttackError> {
+    const FRESH: u64 = 0;
+    const CHECK: u64 = 1;
+    const SWAP: u64 = 2;
+    let (rows, cols) = (instance.redundancy(), instance.code_len());
+    // rows x (cols + 1): the permuted columns, then s
+    let mut mat = BinaryMatrix::zero(rows, cols + 1);
+    let mut mat_t = BinaryMatrix::zero(cols + 1, rows);
+    let mut col_buf = HqcGf2::zero_with_len(rows);
+    let mut tmp_words: Vec<u64> = vec![0u64; HqcGf2::word_len(rows)];
+    let mut perm: Vec<usize> = (0..cols).collect();
+    let max_attempts = rows * (cols - rows);
+
+    let restored = cursor.restore_perm(&mut perm)?;
+    let mut state = match cursor.position[..] {
+        [s @ (CHECK | SWAP)] if restored => {
+            load_instance_columns_transposed(instance, &perm, &mut mat_t, &mut col_buf, &mut tmp_words);
+            mat_t.set_row(cols, instance.syndrome());
+            mat_t.transpose_into(&mut mat);
+            // Rebuilding the saved state is not counted: the stats match an uninterrupted run.
+            if !mat.systematize(rows) {
+                return Err(AttackError::InvalidParameter(
+                    "checkpoint permutation is not an information set".to_string(),
+                ));
+            }
+            s
+        }
+        _ => FRESH,
+    };
+
+    loop {
+        cursor.position = vec![state];
+        cursor.mark(rng, &perm);
+        if stats.iterations >= max_iters {
+            break;
+        }
+        if stats.should_stop(budget) {
+            return Ok(None);
+        }
+        if state == FRESH {
+            sample_cols(rng, &mut perm, rows);
+            load_instance_columns_transposed(instance, &perm, &mut mat_t, &mut col_buf, &mut tmp_words);
+            mat_t.set_row(cols, instance.syndrome());
+            mat_t.transpose_into(&mut mat);
+            if stats.record_elimination(mat.systematize(rows)) {
+                state = CHECK;
+            } else {
+                stats.iterations += 1;
+            }
+            continue;
+        }
+        if state == SWAP {
+            let (mut swapped, mut attempts) = (0, 0);
+            while swapped < c && rows < cols && rows > 0 {
+                if attempts == max_attempts {
+                    break;
+                }
+                if stats.should_stop(budget) {
+                    return Ok(None);
+                }
+                attempts += 1;
+                let k = rng.gen_range(0..rows);
+                let j = rng.gen_range(rows..cols);
+                if pivot_column_swap(&mut mat, &mut perm, k, j) {
+                    swapped += 1;
+                }
+            }
+            if attempts == max_attempts && swapped < c {
+                state = FRESH;
+                continue;
+            }
+        }
+        stats.iterations += 1;
+        stats.candidate_checks += 1;
+        let rhs = mat.column(cols);
+        if let Some(e) = prange_candidate(instance, &perm[..rows], &rhs) {
+            return Ok(Some(e));
+        }
+        state = SWAP;
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,314 @@
+use super::*;
+use crate::gf::gf2::Gf2Construct;
+use crate::gf::hadamard::hadamard_transform;
+use crate::hqc::hqcgf2::HqcGf2;
+use crate::isd::instance::{QcDoubleCirculant, SdAttack, SdInstance};
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+/// Reduction and List Parity Nearly (RLPN) dual decoding, after Carrier–Debris-Alazard–
+/// Meyer-Hilfiger–Tillich.
+///
+/// A parity check is a dual codeword `c = u·H`; then `<c, e> = <u, s>` is known. Split the
+/// positions into a small window P and the rest Q: `<c_P, e_P> + <c_Q, e_Q> = <u, s>`, and when
+/// `c_Q` has low weight `<c_Q, e_Q>` is biased towards 0. So over many checks,
+/// `f(x) = sum_c (-1)^(<u, s> + <c_P, x>)` peaks at `x = e_P`, and the Walsh–Hadamard transform
+/// of the table of `(-1)^<u, s>` indexed by `c_P` evaluates f at all `2^window` guesses at once.
+///
+/// Each iteration is one trial: eliminations with random column permutations put H in
+/// systematic form `[I | A]`, and the sums of `p` rows of weight at most `wq` are collected
+/// until `checks` distinct ones are found (or `checks` eliminations have been spent). The
+/// positions are then cut into windows of `window` bits, each window is decoded by its
+/// transform, and the assembled error is checked. Checks are kept if their total weight is at
+/// most `wq`, so every window uses the same list and its bias is at least that of weight `wq`.
+///
+/// On `[h | I]` the code is quasi-cyclic, so each check `(a, b)` comes with its n rotations
+/// `(X^i·a, X^i·b)`, whose parities are `<X^i·b, s>` (b is u); key recovery adds them, up to
+/// `checks` checks in total.
+pub struct Rlpn {
+    /// Bits decoded per transform; the transform has `2^window` entries.
+    pub window: usize,
+    /// Rows of the systematic form summed per parity check.
+    pub p: usize,
+    /// Maximal weight of a parity check.
+    pub wq: usize,
+    /// Parity checks collected per trial.
+    pub checks: usize,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+/// A dual codeword: its support in original column indices and `<u, s>`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParityCheck {
+    pub support: Vec<usize>,
+    pub parity: bool,
+}
+
+impl Rlpn {
+    pub fn new(
+        window: usize,
+        p: usize,
+        wq: usize,
+        checks: usize,
+        max_iters: Option<u64>,
+        seed: Option<u64>,
+    ) -> Self {
+        Self { window, p, wq, checks, max_iters, seed }
+    }
+}
+
+impl Default for Rlpn {
+    fn default() -> Self {
+        Self {
+            window: 10,
+            p: 2,
+            wq: 40,
+            checks: 4096,
+            max_iters: Some(1000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for Rlpn {
+    fn name(&self) -> &'static str {
+        "RLPN"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for Rlpn {
+    fn config(&self) -> String {
+        format!(
+            "window={} p={} wq={} checks={}",
+            self.window, self.p, self.wq, self.checks
+        )
+    }
+
+    /// The checkpoint marks the start of a trial; a trial cut short by the budget is redone.
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let instance = QcDoubleCirculant::new(h.clone(), s.clone(), w)?;
+        let e = self.run(&instance, Some(&instance), budget, cursor, stats)?;
+        Ok(e.map(|e| instance.y_of(&e)))
+    }
+}
+
+impl SdAttack for Rlpn {
+    fn solve_instance<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        self.run(instance, None, budget, &mut Cursor::default(), stats)
+    }
+}
+
+impl Rlpn {
+    /// `qc` is the instance again when it is `[h | I]`, to add rotated checks.
+    fn run<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        qc: Option<&QcDoubleCirculant>,
+        budget: &AttackBudget,
+        cursor: &mut Cursor,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        let rows = instance.redundancy();
+        if self.window == 0 || self.window > 24 {
+            return Err(AttackError::InvalidParameter(
+                "parameter window must be in 1..=24".to_string(),
+            ));
+        }
+        if self.p == 0 || self.p > rows || self.p > self.wq {
+            return Err(AttackError::InvalidParameter(
+                "parameter p must be > 0, <= n - k and <= wq".to_string(),
+            ));
+        }
+        if self.checks == 0 {
+            return Err(AttackError::InvalidParameter(
+                "parameter checks must be > 0".to_string(),
+            ));
+        }
+        let max_iters = self.max_iters.unwrap_or(u64::MAX);
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        let mut form = SemiSystematicForm::for_instance(instance, 0);
+        cursor.restore_perm(&mut form.perm)?;
+        if !stats.table_fits(budget, self.checks as f64, table_entry_bytes(self.wq)) {
+            return Ok(None);
+        }
+
+        loop {
+            cursor.mark(&rng, &form.perm);
+            if stats.iterations >= max_iters {
+                break;
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+
+            let mut pool = HashSet::new();
+            let mut spent = 0;
+            while pool.len() < self.checks && spent < self.checks {
+                if let Some(reason) = budget.interrupted() {
+                    stats.stopped = Some(reason);
+                    return Ok(None);
+                }
+                spent += 1;
+                if !stats.record_elimination(form.reduce_instance(&mut rng, instance)) {
+                    continue;
+                }
+                collect_parity_checks(&form, self.p, self.wq, |check| {
+                    if let Some(qc) = qc {
+                        for rotated in rotations(&check, qc) {
+                            if pool.len() + 1 >= self.checks {
+                                break;
+                            }
+                            pool.insert(rotated);
+                        }
+                    }
+                    pool.insert(check);
+                    if pool.len() >= self.checks {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                });
+            }
+            stats.record_table(pool.len());
+            let pool: Vec<ParityCheck> = pool.into_iter().collect();
+
+            stats.candidate_checks += 1;
+            let e = decode_windows(&pool, instance.code_len(), self.window);
+            if instance.is_solution(&e) {
+                return Ok(Some(e));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Hand to `f` the sums of p rows of the systematic form `[I | A]` (pivot rows of `form`
+/// with `l = 0`) that have weight at most `wq`, until it breaks.
+pub fn collect_parity_checks(
+    form: &SemiSystematicForm,
+    p: usize,
+    wq: usize,
+    mut f: impl FnMut(ParityCheck) -> ControlFlow<()>,
+) {
+    let rows = form.rows - form.l;
+    let mut a_rows: Vec<HqcGf2> = (0..rows)
+        .map(|_| HqcGf2::zero_with_len(form.info_len()))
+        .collect();
+    for (c, top) in form.tops.iter().enumerate().skip(form.l) {
+        for i in top.ones_indices() {
+            a_rows[i].set(c - form.l);
+        }
+    }
+    let mut acc = HqcGf2::zero_with_len(form.info_len());
+    let _ = for_each_combination_cf(rows, p, |picked| {
+        acc.clear_all();
+        for &i in picked {
+            acc.xor_in_place(&a_rows[i]);
+        }
+        if p + acc.weight() as usize > wq {
+            return ControlFlow::Continue(());
+        }
+        let mut support: Vec<usize> = picked
+            .iter()
+            .map(|&i| form.perm[i])
+            .chain(acc.ones_indices().into_iter().map(|c| form.perm[form.rows + c]))
+            .collect();
+        support.sort_unstable();
+        let parity = picked.iter().fold(false, |b, &i| b ^ form.rhs_top.get(i));
+        f(ParityCheck { support, parity })
+    });
+}
+
+/// The rotations `(X^i·a, X^i·b)`, `i` in `1..n`, of a check `(a, b)` of `[h | I]`.
+pub fn rotations<'a>(check: &'a ParityCheck, qc: &'a QcDoubleCirculant) -> impl Iterator<Item = ParityCheck> + 'a {
+    let n = qc.h.n;
+    (1..n).map(move |i| {
+        let mut support: Vec<usize> = check
+            .support
+            .iter()
+            .map(|&c| (c / n) * n + (c % n + i) % n)
+            .collect();
+        support.sort_unstable();
+        let parity = support
+            .iter()
+            .filter(|&&c| c >= n)
+            .fold(false, |b, &c| b ^ qc.s.get(c - n));
+        ParityCheck { support, parity }
+    })
+}
+
+/// Decode every window of `window` consecutive positions by the Walsh–Hadamard transform of
+/// `pool` restricted to it, and return the assembled error of length `len`.
+pub fn decode_windows(pool: &[ParityCheck], len: usize, window: usize) -> HqcGf2 {
+    let mut e = HqcGf2::zero_with_len(len);
+    for start in (0..len).step_by(window) {
+        let width = window.min(len - start);
+        for bit in decode_window(pool, start, width) {
+            e.set(start + bit);
+        }
+    }
+    e
+}
+
+/// Offsets in `start..start + width` of the most likely error pattern on that window: the
+/// argmax of `f(x) = sum_c (-1)^(<u, s> + <c_P, x>)`, ties going to the lighter guess.
+pub fn decode_window(pool: &[ParityCheck], start: usize, width: usize) -> Vec<usize> {
+    let mut table = vec![0i32; 1 << width];
+    for check in pool {
+        let z = check
+            .support
+            .iter()
+            .filter(|&&c| c >= start && c < start + width)
+            .fold(0usize, |z, &c| z | 1 << (c - start));
+        table[z] += if check.parity { -1 } else { 1 };
+    }
+    hadamard_transform(&mut table);
+    let best = (0..table.len())
+        .max_by(|&a, &b| {
+            table[a]
+                .cmp(&table[b])
+                .then((b.count_ones()).cmp(&a.count_ones()))
+                .then(b.cmp(&a))
+        })
+        .unwrap_or(0);
+    (0..width).filter(|&bit| best >> bit & 1 == 1).collect()
+}
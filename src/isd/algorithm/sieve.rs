@@ -0,0 +1,353 @@
+use super::*;
+use crate::isd::attack::AttackReport;
+use crate::isd::estimator::log2_binomial;
+use crate::hqc::hqcgf2::HqcGf2;
+use crate::isd::instance::{QcDoubleCirculant, SdAttack, SdInstance};
+use rand::Rng;
+use rand::seq::index::sample;
+use std::collections::HashSet;
+use std::time::Instant;
+
+/// Sieving-style ISD on `[h | I]`, after Guo–Johansson–Nguyen.
+///
+/// Each iteration permutes the 2n columns and reduces `n - l` of them to identity, then looks
+/// for the weight-p part of the error on the `n + l` non-pivot columns. The syndrome is
+/// appended as an extra column (index `n + l`), so the target becomes a low-weight vector
+/// of the augmented code that contains that column.
+///
+/// Level 0 is a list of random weight-p vectors, half of them containing the syndrome column.
+/// Level i keeps the sums `a + b` of level i - 1 that have weight p again (overlapping ones
+/// cancel, as in representation techniques) and vanish on the first `l·i / levels` window
+/// bits. Every list holds at most `list_len()` vectors, set by `memory_bytes`, so memory
+/// stays constant while the lists close in on the solution. The last level vanishes on all
+/// l bits; its vectors with the syndrome column are completed like Stern's collisions.
+pub struct Sieve {
+    pub p: usize,
+    pub l: usize,
+    pub levels: usize,
+    /// Memory for the three lists alive at a time: the current level, the next one and the
+    /// set of supports already in the next one.
+    pub memory_bytes: usize,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
+}
+
+/// Sizes of one sieve level over the iterations that reached it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LevelSizes {
+    /// Iterations that built this level.
+    pub samples: u64,
+    /// Sum of the list lengths.
+    pub total: u64,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl LevelSizes {
+    fn record(&mut self, len: usize) {
+        self.min = if self.samples == 0 { len } else { self.min.min(len) };
+        self.max = self.max.max(len);
+        self.total += len as u64;
+        self.samples += 1;
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.samples == 0 {
+            0.0
+        } else {
+            self.total as f64 / self.samples as f64
+        }
+    }
+}
+
+impl Sieve {
+    pub fn new(
+        p: usize,
+        l: usize,
+        levels: usize,
+        memory_bytes: usize,
+        max_iters: Option<u64>,
+        seed: Option<u64>,
+    ) -> Self {
+        Self { p, l, levels, memory_bytes, max_iters, seed }
+    }
+
+    /// Maximal length of one list: a third of `memory_bytes`, in entries of p columns plus
+    /// the syndrome column.
+    pub fn list_len(&self) -> usize {
+        self.memory_bytes / (3 * table_entry_bytes(self.p + 1))
+    }
+
+    /// Window bits the vectors of level i vanish on.
+    pub fn level_bits(&self, i: usize) -> usize {
+        self.l * i / self.levels
+    }
+
+    /// `report_within`, also returning the list sizes of levels `0..=levels`.
+    pub fn report_levels_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+    ) -> Result<(AttackReport, Vec<LevelSizes>), AttackError> {
+        let start = Instant::now();
+        let mut stats = AttackStats::default();
+        let mut levels = vec![LevelSizes::default(); self.levels + 1];
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        let instance = QcDoubleCirculant::new(h.clone(), s.clone(), w)?;
+        let e = self.run(&instance, budget, &mut Cursor::default(), &mut stats, &mut levels)?;
+        let report = AttackReport {
+            solution: e.map(|e| instance.y_of(&e)),
+            stats,
+            wall_time: start.elapsed(),
+        };
+        Ok((report, levels))
+    }
+}
+
+impl Default for Sieve {
+    fn default() -> Self {
+        Self {
+            p: 2,
+            l: 12,
+            levels: 2,
+            memory_bytes: 1 << 24,
+            max_iters: Some(200000),
+            seed: None,
+        }
+    }
+}
+
+impl Attack for Sieve {
+    fn name(&self) -> &'static str {
+        "Sieve"
+    }
+
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for Sieve {
+    fn config(&self) -> String {
+        format!(
+            "p={} l={} levels={} memory={}",
+            self.p, self.l, self.levels, self.memory_bytes
+        )
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        if h.n != n || s.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n or s.n != n".to_string(),
+            ));
+        }
+        if n == 0 {
+            return Ok(None);
+        }
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let instance = QcDoubleCirculant::new(h.clone(), s.clone(), w)?;
+        let mut levels = vec![LevelSizes::default(); self.levels + 1];
+        let e = self.run(&instance, budget, cursor, stats, &mut levels)?;
+        Ok(e.map(|e| instance.y_of(&e)))
+    }
+}
+
+impl SdAttack for Sieve {
+    fn solve_instance<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        let mut levels = vec![LevelSizes::default(); self.levels + 1];
+        self.run(instance, budget, &mut Cursor::default(), stats, &mut levels)
+    }
+}
+
+impl Sieve {
+    fn run<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        cursor: &mut Cursor,
+        stats: &mut AttackStats,
+        levels: &mut [LevelSizes],
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        let rows = instance.redundancy();
+        if self.l > 64 || self.l > rows {
+            return Err(AttackError::InvalidParameter(
+                "parameter l must be <= 64 and <= n - k".to_string(),
+            ));
+        }
+        if self.levels == 0 || self.levels > self.l {
+            return Err(AttackError::InvalidParameter(
+                "parameter levels must be > 0 and <= l".to_string(),
+            ));
+        }
+        let len = instance.code_len() - rows + self.l;
+        if self.p > instance.weight() || self.p > len {
+            return Err(AttackError::InvalidParameter(
+                "parameter p must be <= weight and <= k + l".to_string(),
+            ));
+        }
+        if self.list_len() == 0 {
+            return Err(AttackError::InvalidParameter(
+                "parameter memory_bytes is too small for one list entry".to_string(),
+            ));
+        }
+        let max_iters = self.max_iters.unwrap_or(u64::MAX);
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        let mut form = SemiSystematicForm::for_instance(instance, self.l);
+        cursor.restore_perm(&mut form.perm)?;
+        if !stats.table_fits(budget, 3.0 * self.list_len() as f64, table_entry_bytes(self.p + 1)) {
+            return Ok(None);
+        }
+
+        loop {
+            cursor.mark(&rng, &form.perm);
+            if stats.iterations >= max_iters {
+                break;
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            if !stats.record_elimination(form.reduce_instance(&mut rng, instance)) {
+                continue;
+            }
+            let found = self.sieve_with(&form, &mut rng, stats, levels, |cols| {
+                form.recover_error(cols, instance)
+            });
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// One sieve on a reduced form: build the levels and hand the non-pivot columns of each
+    /// final vector containing the syndrome column to `recover`.
+    pub fn sieve_with<T, R: Rng>(
+        &self,
+        form: &SemiSystematicForm,
+        rng: &mut R,
+        stats: &mut AttackStats,
+        levels: &mut [LevelSizes],
+        mut recover: impl FnMut(&[usize]) -> Option<T>,
+    ) -> Option<T> {
+        let len = form.non_pivot_len();
+        let cap = self.list_len();
+        let mut list = self.initial_list(form, rng, cap);
+        stats.record_table(list.len());
+        levels[0].record(list.len());
+        for i in 1..=self.levels {
+            list = self.sieve_level(&mut list, len, low_mask(self.level_bits(i)), cap, stats);
+            stats.record_table(list.len());
+            levels[i].record(list.len());
+            if list.is_empty() {
+                return None;
+            }
+        }
+        for entry in &list {
+            if entry.cols.last() == Some(&len) {
+                stats.candidate_checks += 1;
+                if let Some(found) = recover(&entry.cols[..self.p]) {
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+
+    /// Up to `cap` distinct random weight-p vectors, each containing the syndrome column with
+    /// probability 1/2.
+    fn initial_list<R: Rng>(&self, form: &SemiSystematicForm, rng: &mut R, cap: usize) -> Vec<ListEntry> {
+        let len = form.non_pivot_len();
+        let distinct = log2_binomial(len, self.p) + 1.0;
+        let target = if distinct < 63.0 { cap.min(distinct.exp2() as usize) } else { cap };
+        let mut seen = HashSet::with_capacity(target);
+        let mut out = Vec::with_capacity(target);
+        let mut tries = 0;
+        while out.len() < target && tries < 4 * target {
+            tries += 1;
+            let mut cols = sample(rng, len, self.p).into_vec();
+            cols.sort_unstable();
+            if rng.r#gen::<bool>() {
+                cols.push(len);
+            }
+            if seen.insert(cols.clone()) {
+                out.push(ListEntry { key: augmented_key(form, &cols), cols });
+            }
+        }
+        out
+    }
+
+    /// Sums of two entries of `list` that have weight p outside `syndrome_col` and vanish on
+    /// `mask`; the first `cap` of them in key order.
+    fn sieve_level(
+        &self,
+        list: &mut [ListEntry],
+        syndrome_col: usize,
+        mask: u64,
+        cap: usize,
+        stats: &mut AttackStats,
+    ) -> Vec<ListEntry> {
+        list.sort_unstable_by_key(|e| e.key & mask);
+        let mut seen = HashSet::new();
+        let mut out = Vec::new();
+        let mut start = 0;
+        'buckets: while start < list.len() {
+            let key = list[start].key & mask;
+            let end = start + list[start..].iter().take_while(|e| e.key & mask == key).count();
+            for a in start..end {
+                for b in a + 1..end {
+                    stats.candidate_checks += 1;
+                    let cols = xor_supports(&list[a].cols, &list[b].cols);
+                    let weight = cols.len() - usize::from(cols.last() == Some(&syndrome_col));
+                    if weight != self.p || !seen.insert(cols.clone()) {
+                        continue;
+                    }
+                    out.push(ListEntry { key: list[a].key ^ list[b].key, cols });
+                    if out.len() >= cap {
+                        break 'buckets;
+                    }
+                }
+            }
+            start = end;
+        }
+        out
+    }
+}
+
+/// Window key of a vector of the augmented code: column `non_pivot_len()` is the syndrome.
+fn augmented_key(form: &SemiSystematicForm, cols: &[usize]) -> u64 {
+    let len = form.non_pivot_len();
+    cols.iter()
+        .fold(0u64, |acc, &c| acc ^ if c == len { form.rhs_key } else { form.keys[c] })
+}
@@ -1,39 +1,36 @@
 use super::*;
-use crate::hqc::hash::xof::Shake256Xof;
+use crate::isd::estimator::log2_binomial;
 use crate::hqc::hqcgf2::HqcGf2;
-use crate::hqc::sampling::rand_bits;
-use std::collections::HashSet;
+use crate::isd::instance::{QcDoubleCirculant, SdAttack, SdInstance};
 use std::collections::HashMap;
 use std::ops::ControlFlow;
+
+/// Stern's ISD on `[h | I]`.
+///
+/// Each iteration permutes the 2n columns, reduces `n - l` of them to identity and keeps
+/// `l` rows as a collision window. The information set is split into two halves, and
+/// p-subsets of each half are collided on the l window bits. The remaining `2w - 2p`
+/// errors must sit on the pivot columns, none on the window columns.
 pub struct Stern {
-    window_size: usize,
-    bound: usize,
-    window_tries: usize,
-    seed: Vec<u8>,
-    cap_per_key: usize,
+    pub p: usize,
+    pub l: usize,
+    pub max_iters: Option<u64>,
+    pub seed: Option<u64>,
 }
 
 impl Stern {
-    pub fn new(window_size: usize, bound: usize, window_tries: usize, seed: Vec<u8>, cap_per_key: usize) -> Self {
-        assert!(window_size > 0, "window_size must be positive");
-        Self {
-            window_size,
-            bound,
-            window_tries,
-            seed,
-            cap_per_key,
-        }
+    pub fn new(p: usize, l: usize, max_iters: Option<u64>, seed: Option<u64>) -> Self {
+        Self { p, l, max_iters, seed }
     }
 }
 
 impl Default for Stern {
     fn default() -> Self {
         Self {
-            window_size: 100,
-            bound: 4,
-            window_tries: 100,
-            seed: "default_seed".as_bytes().to_vec(),
-            cap_per_key: 100,
+            p: 1,
+            l: 12,
+            max_iters: Some(200000),
+            seed: None,
         }
     }
 }
@@ -43,12 +40,32 @@ impl Attack for Stern {
         "Stern"
     }
 
-    fn solve(
+    fn solve_within(
         &self,
         n: usize,
         w: usize,
         h: &HqcGf2,
         s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        solve_fresh(self, n, w, h, s, budget, stats)
+    }
+}
+
+impl Resumable for Stern {
+    fn config(&self) -> String {
+        format!("p={} l={}", self.p, self.l)
+    }
+
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
     ) -> Result<Option<HqcGf2>, AttackError> {
         if h.n != n || s.n != n {
             return Err(AttackError::InvalidParameter(
@@ -58,129 +75,134 @@ impl Attack for Stern {
         if n == 0 {
             return Ok(None);
         }
-        if self.window_size == 0 || self.window_size > n - w {
+        if self.l > 64 || self.l > n {
             return Err(AttackError::InvalidParameter(
-                "parameter window_size must be > 0 and <= n - w".to_string(),
+                "parameter l must be <= 64 and <= n".to_string(),
             ));
         }
-        for i in 0..self.window_tries {
-            let mut seed = Vec::with_capacity(self.seed.len() + 8);
-            seed.extend_from_slice(&self.seed);
-            seed.extend_from_slice(&(i as u64).to_le_bytes());
-            let window = select_window(n, self.window_size, &seed);
-            let mid = w / 2;
-            for delta in 0..=self.bound {
-                for sign in [0i32, 1i32] {
-                    let p1 = match sign {
-                        0 => mid.saturating_add(delta),
-                        _ => mid.saturating_sub(delta),
-                    };
-                    if p1 > w {
-                        continue;
-                    }
-                    let p2 = w - p1;
-                    let n1 = n / 2;
-                    let n2 = n - n1;
-                    if p1 > n1 || p2 > n2 {
-                        continue;
-                    }
-                    if let Some(y) =
-                        stern_try_once(n, w, h, s, n1, n2, p1, p2, &window, self.cap_per_key)
-                    {
-                        return Ok(Some(y));
-                    }
-                }
-            }
+        if self.p > w || self.p > n / 2 {
+            return Err(AttackError::InvalidParameter(
+                "parameter p must be <= w and <= n / 2".to_string(),
+            ));
         }
-        Ok(None)
+
+        checkpoint.claim(self, n, w)?;
+        let Checkpoint { cursor, stats, .. } = checkpoint;
+        let instance = QcDoubleCirculant::new(h.clone(), s.clone(), w)?;
+        let e = self.run(&instance, budget, cursor, stats)?;
+        Ok(e.map(|e| instance.y_of(&e)))
     }
 }
 
-/// Select a random integer in [0, bound)
-fn rand_bounded(xof: &Shake256Xof, bound: usize) -> usize {
-    debug_assert!(bound > 0);
-    let b = bound as u64;
-    let limit = (u64::MAX / b) * b;
-    loop {
-        let r = rand_bits(xof) as u64;
-        if r < limit {
-            return (r % b) as usize;
-        }
+impl SdAttack for Stern {
+    fn solve_instance<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        self.run(instance, budget, &mut Cursor::default(), stats)
     }
 }
 
-/// Select a random window of given size
-pub fn select_window(n: usize, window_size: usize, seed: &[u8]) -> Vec<usize> {
-    assert!(window_size <= n, "window_size must be <= n");
-    let xof = Shake256Xof::new(seed);
-    let k = window_size;
-    let mut chosen: HashSet<usize> = HashSet::with_capacity(k * 2);
-    for j in (n - k)..n {
-        let t = rand_bounded(&xof, j + 1);
-        if !chosen.insert(t) {
-            chosen.insert(j);
+impl Stern {
+    fn run<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        cursor: &mut Cursor,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        let rows = instance.redundancy();
+        let info = instance.code_len().saturating_sub(rows);
+        if self.l > 64 || self.l > rows {
+            return Err(AttackError::InvalidParameter(
+                "parameter l must be <= 64 and <= n - k".to_string(),
+            ));
+        }
+        if 2 * self.p > instance.weight() || self.p > info / 2 {
+            return Err(AttackError::InvalidParameter(
+                "parameter p must be <= weight / 2 and <= k / 2".to_string(),
+            ));
+        }
+        let max_iters = self.max_iters.unwrap_or(u64::MAX);
+        let mut rng = cursor.rng(self.seed);
+        stats.seed = cursor.seed;
+        let mut form = SemiSystematicForm::for_instance(instance, self.l);
+        cursor.restore_perm(&mut form.perm)?;
+        let entries = log2_binomial(info / 2, self.p).exp2();
+        if !stats.table_fits(budget, entries, table_entry_bytes(self.p)) {
+            return Ok(None);
+        }
+
+        loop {
+            cursor.mark(&rng, &form.perm);
+            if stats.iterations >= max_iters {
+                break;
+            }
+            if stats.should_stop(budget) {
+                return Ok(None);
+            }
+            stats.iterations += 1;
+            if !stats.record_elimination(form.reduce_instance(&mut rng, instance)) {
+                continue;
+            }
+            let found = stern_collide_with(&form, self.p, stats, |cols| form.recover_error(cols, instance));
+            if found.is_some() {
+                return Ok(found);
+            }
         }
+
+        Ok(None)
     }
-    let mut window: Vec<usize> = chosen.into_iter().collect();
-    window.sort_unstable();
-    window
 }
 
-pub fn stern_try_once(
-    n: usize,
+/// Collide p-subsets of the two information-set halves on the window bits.
+pub fn stern_collide(
+    form: &SemiSystematicForm,
+    p: usize,
     w: usize,
     h: &HqcGf2,
     s: &HqcGf2,
-    n1: usize,
-    n2: usize,
-    p1: usize,
-    p2: usize,
-    window: &[usize],
-    cap_per_key: usize,
+    stats: &mut AttackStats,
 ) -> Option<HqcGf2> {
-    if n1 + n2 != n {
-        return None;
-    }
-    if p1 > n1 || p2 > n2 {
-        return None;
-    }
-    let key_words = (window.len() + 63) / 64;
-    let mut key_buf = vec![0u64; key_words];
-    let mut rhs_buf = vec![0u64; key_words];
-    let mut table: HashMap<Vec<u64>, Vec<Vec<usize>>> = HashMap::new();
-    let _ = for_each_combination_cf(n1, p1, |support_y1| {
-        let y1: Vec<usize> = support_y1.to_vec();
-
-        h_mul_y_on_window(n, h, &y1, window, &mut key_buf);
-        let key = key_buf.clone();
-
-        let entry = table.entry(key).or_default();
-        if entry.len() < cap_per_key {
-            entry.push(y1);
-        }
+    stern_collide_with(form, p, stats, |cols| form.recover(cols, w, h, s))
+}
 
+/// `stern_collide` with the candidate check `recover` on the non-pivot columns of each
+/// collision.
+pub fn stern_collide_with<T>(
+    form: &SemiSystematicForm,
+    p: usize,
+    stats: &mut AttackStats,
+    mut recover: impl FnMut(&[usize]) -> Option<T>,
+) -> Option<T> {
+    let l = form.l;
+    let n1 = form.info_len() / 2;
+    let n2 = form.info_len() - n1;
+    let mut table: HashMap<u64, Vec<Vec<usize>>> = HashMap::new();
+    let mut entries = 0;
+    let _ = for_each_combination_cf(n1, p, |support1| {
+        let cols: Vec<usize> = support1.iter().map(|&i| l + i).collect();
+        table.entry(form.key_of(&cols)).or_default().push(cols);
+        entries += 1;
         ControlFlow::Continue(())
     });
+    stats.record_table(entries);
 
-    let mut answer: Option<HqcGf2> = None;
-    let _ = for_each_combination_cf(n2, p2, |support_y2| {
-        if answer.is_some() {
-            return ControlFlow::Break(());
-        }
-        let y2: Vec<usize> = support_y2.iter().map(|&i| n1 + i).collect();
-        s_xor_h_mul_y_key_on_window(n, s, h, &y2, window, &mut rhs_buf);
-
-        if let Some(cands) = table.get(&rhs_buf) {
-            for y1 in cands {
-                let mut supp_y = Vec::with_capacity(y1.len() + y2.len());
-                supp_y.extend_from_slice(y1);
-                supp_y.extend_from_slice(&y2);
-                let y = HqcGf2::from_indices(n, &supp_y);
-                let hy = h.mul_bitpacked(&y);
-                let mut x = s.clone();
-                x.xor_in_place(&hy);
-                if x.weight() == w.try_into().unwrap() {
-                    answer = Some(y);
+    let mut answer = None;
+    let mut cols = Vec::with_capacity(2 * p);
+    let _ = for_each_combination_cf(n2, p, |support2| {
+        cols.clear();
+        cols.extend(support2.iter().map(|&i| l + n1 + i));
+        let key = form.rhs_key ^ form.key_of(&cols);
+        if let Some(cands) = table.get(&key) {
+            for cols1 in cands {
+                cols.truncate(p);
+                cols.extend_from_slice(cols1);
+                stats.candidate_checks += 1;
+                if let Some(found) = recover(&cols) {
+                    answer = Some(found);
                     return ControlFlow::Break(());
                 }
             }
@@ -188,4 +210,4 @@ pub fn stern_try_once(
         ControlFlow::Continue(())
     });
     answer
-}
\ No newline at end of file
+}
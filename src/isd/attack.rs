@@ -1,7 +1,78 @@
+use super::budget::{AttackBudget, StopReason};
 use super::error::AttackError;
 use crate::hqc::hqcgf2::HqcGf2;
+use std::time::{Duration, Instant};
+
+/// Work counters recorded by `Attack::solve_with_stats`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AttackStats {
+    /// RNG seed actually used, None for deterministic attacks.
+    pub seed: Option<u64>,
+    /// Outer-loop iterations (information sets, windows or combinations tried).
+    pub iterations: u64,
+    /// Gaussian eliminations started, including singular ones.
+    pub eliminations: u64,
+    /// Eliminations skipped because the selected columns were singular.
+    pub singular_skips: u64,
+    /// Entries inserted into collision tables and merge lists, summed over iterations.
+    pub table_entries: u64,
+    /// Size of the largest single collision table or merge list.
+    pub max_table_len: usize,
+    /// Candidates whose weight (and equation) was checked.
+    pub candidate_checks: u64,
+    /// Set when the budget stopped the run.
+    pub stopped: Option<StopReason>,
+}
+
+impl AttackStats {
+    /// Record a collision table or merge list of `len` entries.
+    pub fn record_table(&mut self, len: usize) {
+        self.table_entries += len as u64;
+        self.max_table_len = self.max_table_len.max(len);
+    }
+
+    /// Record one elimination, returning `ok` for use as a loop guard.
+    pub fn record_elimination(&mut self, ok: bool) -> bool {
+        self.eliminations += 1;
+        if !ok {
+            self.singular_skips += 1;
+        }
+        ok
+    }
+}
+
+/// Outcome of `Attack::solve_with_report`.
+#[derive(Clone, Debug)]
+pub struct AttackReport {
+    pub solution: Option<HqcGf2>,
+    pub stats: AttackStats,
+    pub wall_time: Duration,
+}
+
 pub trait Attack {
     fn name(&self) -> &'static str;
+    /// Same as `solve`, stopping early with `Ok(None)` once `budget` is exhausted and
+    /// recording the work done into `stats`.
+    fn solve_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError>;
+    /// Same as `solve`, recording the work done into `stats`.
+    fn solve_with_stats(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        self.solve_within(n, w, h, s, &AttackBudget::unlimited(), stats)
+    }
     /// output y' has weight w if successful, None otherwise
     fn solve(
         &self,
@@ -9,5 +80,35 @@ pub trait Attack {
         w: usize,
         h: &HqcGf2,
         s: &HqcGf2,
-    ) -> Result<Option<HqcGf2>, AttackError>;
+    ) -> Result<Option<HqcGf2>, AttackError> {
+        self.solve_with_stats(n, w, h, s, &mut AttackStats::default())
+    }
+    /// Run the attack and report the solution together with its counters and wall time.
+    fn solve_with_report(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+    ) -> Result<AttackReport, AttackError> {
+        self.report_within(n, w, h, s, &AttackBudget::unlimited())
+    }
+    /// `solve_with_report` under `budget`; `stats.stopped` tells whether the budget ran out.
+    fn report_within(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+    ) -> Result<AttackReport, AttackError> {
+        let start = Instant::now();
+        let mut stats = AttackStats::default();
+        let solution = self.solve_within(n, w, h, s, budget, &mut stats)?;
+        Ok(AttackReport {
+            solution,
+            stats,
+            wall_time: start.elapsed(),
+        })
+    }
 }
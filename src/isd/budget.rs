@@ -0,0 +1,132 @@
+use super::attack::AttackStats;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Shared flag a harness sets to stop running attacks. Clones share the same flag.
+#[derive(Clone, Debug, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Why a run stopped before finding a solution or exhausting its own search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    Cancelled,
+    Deadline,
+    IterationCap,
+    MemoryCap,
+}
+
+/// Limits shared by every `Attack`. The attack's own `max_iters` still applies; the budget
+/// is checked before each iteration, and the memory cap before building collision tables.
+#[derive(Clone, Debug, Default)]
+pub struct AttackBudget {
+    pub deadline: Option<Instant>,
+    pub max_iters: Option<u64>,
+    /// Upper bound on the estimated size of the largest table or list, in bytes.
+    pub max_memory_bytes: Option<usize>,
+    pub cancel: Option<CancelFlag>,
+}
+
+impl AttackBudget {
+    /// No limits.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Stop `limit` from now.
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.deadline = Some(Instant::now() + limit);
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = Some(max_iters);
+        self
+    }
+
+    pub fn with_max_memory_bytes(mut self, bytes: usize) -> Self {
+        self.max_memory_bytes = Some(bytes);
+        self
+    }
+
+    pub fn with_cancel(mut self, cancel: CancelFlag) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Some(reason) if no further iteration may start.
+    pub fn exhausted(&self, stats: &AttackStats) -> Option<StopReason> {
+        if let Some(reason) = self.interrupted() {
+            return Some(reason);
+        }
+        if self.max_iters.is_some_and(|m| stats.iterations >= m) {
+            return Some(StopReason::IterationCap);
+        }
+        None
+    }
+
+    /// Some(reason) if the run was cancelled or is past its deadline: the limits that also
+    /// apply within an iteration.
+    pub fn interrupted(&self) -> Option<StopReason> {
+        if self.cancel.as_ref().is_some_and(CancelFlag::is_cancelled) {
+            return Some(StopReason::Cancelled);
+        }
+        if self.deadline.is_some_and(|d| Instant::now() >= d) {
+            return Some(StopReason::Deadline);
+        }
+        None
+    }
+
+    /// Whether a table of `entries` entries of `entry_bytes` bytes each fits the memory cap.
+    pub fn allows_table(&self, entries: f64, entry_bytes: usize) -> bool {
+        self.max_memory_bytes
+            .is_none_or(|cap| entries * entry_bytes as f64 <= cap as f64)
+    }
+}
+
+/// Rough heap size of one table entry holding `cols` column indices.
+pub fn table_entry_bytes(cols: usize) -> usize {
+    48 + cols * std::mem::size_of::<usize>()
+}
+
+impl AttackStats {
+    /// Check the budget before an iteration, recording the stop reason if exhausted.
+    pub fn should_stop(&mut self, budget: &AttackBudget) -> bool {
+        match budget.exhausted(self) {
+            Some(reason) => {
+                self.stopped = Some(reason);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check a table of `entries` entries against the memory cap, recording the stop reason
+    /// if it does not fit.
+    pub fn table_fits(&mut self, budget: &AttackBudget, entries: f64, entry_bytes: usize) -> bool {
+        if budget.allows_table(entries, entry_bytes) {
+            return true;
+        }
+        self.stopped = Some(StopReason::MemoryCap);
+        false
+    }
+}
@@ -0,0 +1,296 @@
+use super::attack::{Attack, AttackStats};
+use super::budget::{AttackBudget, StopReason};
+use super::error::{AttackError, CheckpointError};
+use crate::hqc::hqcgf2::HqcGf2;
+use rand::rngs::OsRng;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use std::fmt::Write as _;
+use std::path::Path;
+
+const MAGIC: &str = "isd4hqc-checkpoint v1";
+
+/// Where an attack stopped: enough to continue the same random walk.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cursor {
+    /// Seed of the attack's RNG; drawn on the first run if the attack has none.
+    pub seed: Option<u64>,
+    /// Position of the RNG stream, in 32-bit words.
+    pub rng_word_pos: u128,
+    /// The column permutation carried from one iteration to the next, empty before the first.
+    pub perm: Vec<usize>,
+    /// Attack-specific position (window/delta/sign for `BirthdaySearch`, the current
+    /// combination for `BruteForce`).
+    pub position: Vec<u64>,
+}
+
+impl Cursor {
+    /// RNG continuing from this cursor. Fixes `self.seed` to `seed` (or a fresh one) on the
+    /// first run.
+    pub fn rng(&mut self, seed: Option<u64>) -> ChaCha12Rng {
+        let seed = *self
+            .seed
+            .get_or_insert_with(|| seed.unwrap_or_else(|| OsRng.next_u64()));
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        rng.set_word_pos(self.rng_word_pos);
+        rng
+    }
+
+    /// Record the state at an iteration boundary.
+    pub fn mark(&mut self, rng: &ChaCha12Rng, perm: &[usize]) {
+        self.rng_word_pos = rng.get_word_pos();
+        self.perm.clear();
+        self.perm.extend_from_slice(perm);
+    }
+
+    /// Copy the saved permutation into `perm` if there is one. Err if it is not a permutation
+    /// of `perm.len()` columns.
+    pub fn restore_perm(&self, perm: &mut [usize]) -> Result<bool, AttackError> {
+        if self.perm.is_empty() {
+            return Ok(false);
+        }
+        let mut seen = vec![false; perm.len()];
+        let valid = self.perm.len() == perm.len()
+            && self
+                .perm
+                .iter()
+                .all(|&c| c < seen.len() && !std::mem::replace(&mut seen[c], true));
+        if !valid {
+            return Err(AttackError::InvalidParameter(
+                "checkpoint permutation does not match n".to_string(),
+            ));
+        }
+        perm.copy_from_slice(&self.perm);
+        Ok(true)
+    }
+}
+
+/// Saved state of a resumable attack run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// `Attack::name` of the attack that produced it, empty for a fresh checkpoint.
+    pub attack: String,
+    /// `Resumable::config` of that attack.
+    pub config: String,
+    pub n: usize,
+    pub w: usize,
+    pub cursor: Cursor,
+    /// Statistics accumulated over all runs so far.
+    pub stats: AttackStats,
+}
+
+impl Checkpoint {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind a fresh checkpoint to `attack`, or check that a loaded one belongs to it.
+    pub fn claim<A: Resumable + ?Sized>(
+        &mut self,
+        attack: &A,
+        n: usize,
+        w: usize,
+    ) -> Result<(), AttackError> {
+        let config = attack.config();
+        if self.attack.is_empty() {
+            self.attack = attack.name().to_string();
+            self.config = config;
+            self.n = n;
+            self.w = w;
+        } else if self.attack != attack.name()
+            || self.config != config
+            || self.n != n
+            || self.w != w
+        {
+            return Err(AttackError::InvalidParameter(format!(
+                "checkpoint is for {} ({}) on n={}, w={}",
+                self.attack, self.config, self.n, self.w
+            )));
+        }
+        self.stats.stopped = None;
+        Ok(())
+    }
+
+    pub fn to_text(&self) -> String {
+        let st = &self.stats;
+        let join = |v: &mut dyn Iterator<Item = String>| v.collect::<Vec<_>>().join(" ");
+        let mut out = String::new();
+        let _ = writeln!(out, "{}", MAGIC);
+        let _ = writeln!(out, "attack {}", self.attack);
+        let _ = writeln!(out, "config {}", self.config);
+        let _ = writeln!(out, "instance {} {}", self.n, self.w);
+        let _ = writeln!(
+            out,
+            "seed {}",
+            self.cursor.seed.map_or("-".to_string(), |s| s.to_string())
+        );
+        let _ = writeln!(out, "rng_word_pos {}", self.cursor.rng_word_pos);
+        let _ = writeln!(
+            out,
+            "position {}",
+            join(&mut self.cursor.position.iter().map(u64::to_string))
+        );
+        let _ = writeln!(
+            out,
+            "perm {}",
+            join(&mut self.cursor.perm.iter().map(usize::to_string))
+        );
+        let _ = writeln!(
+            out,
+            "stats {} {} {} {} {} {}",
+            st.iterations,
+            st.eliminations,
+            st.singular_skips,
+            st.table_entries,
+            st.max_table_len,
+            st.candidate_checks
+        );
+        let _ = writeln!(
+            out,
+            "stopped {}",
+            st.stopped.map_or("-".to_string(), |r| format!("{:?}", r))
+        );
+        out
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, CheckpointError> {
+        let mut lines = text.lines();
+        if lines.next() != Some(MAGIC) {
+            return Err(malformed("missing header"));
+        }
+        let mut field = |key: &str| -> Result<&str, CheckpointError> {
+            let line = lines
+                .next()
+                .ok_or_else(|| malformed(&format!("missing {}", key)))?;
+            match line.split_once(' ') {
+                Some((k, v)) if k == key => Ok(v),
+                None if line == key => Ok(""),
+                _ => Err(malformed(&format!("expected {}", key))),
+            }
+        };
+        let attack = field("attack")?.to_string();
+        let config = field("config")?.to_string();
+        let instance: Vec<usize> = parse_list(field("instance")?)?;
+        let [n, w] = instance[..] else {
+            return Err(malformed("instance needs n and w"));
+        };
+        let seed = match field("seed")? {
+            "-" => None,
+            v => Some(parse(v)?),
+        };
+        let rng_word_pos = parse(field("rng_word_pos")?)?;
+        let position = parse_list(field("position")?)?;
+        let perm = parse_list(field("perm")?)?;
+        let counters: Vec<u64> = parse_list(field("stats")?)?;
+        let [
+            iterations,
+            eliminations,
+            singular_skips,
+            table_entries,
+            max_table_len,
+            candidate_checks,
+        ] = counters[..]
+        else {
+            return Err(malformed("stats needs six counters"));
+        };
+        let stopped = match field("stopped")? {
+            "-" => None,
+            "Cancelled" => Some(StopReason::Cancelled),
+            "Deadline" => Some(StopReason::Deadline),
+            "IterationCap" => Some(StopReason::IterationCap),
+            "MemoryCap" => Some(StopReason::MemoryCap),
+            other => return Err(malformed(&format!("unknown stop reason {}", other))),
+        };
+        Ok(Self {
+            attack,
+            config,
+            n,
+            w,
+            cursor: Cursor {
+                seed,
+                rng_word_pos,
+                perm,
+                position,
+            },
+            stats: AttackStats {
+                seed,
+                iterations,
+                eliminations,
+                singular_skips,
+                table_entries,
+                max_table_len: max_table_len as usize,
+                candidate_checks,
+                stopped,
+            },
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        // Write a sibling file first so a crash mid-write keeps the previous checkpoint.
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, self.to_text())?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CheckpointError> {
+        Self::from_text(&std::fs::read_to_string(path)?)
+    }
+}
+
+fn malformed(msg: &str) -> CheckpointError {
+    CheckpointError::Malformed(msg.to_string())
+}
+
+fn parse<T: std::str::FromStr>(v: &str) -> Result<T, CheckpointError> {
+    v.trim()
+        .parse()
+        .map_err(|_| malformed(&format!("bad number {:?}", v)))
+}
+
+fn parse_list<T: std::str::FromStr>(v: &str) -> Result<Vec<T>, CheckpointError> {
+    v.split_whitespace().map(parse).collect()
+}
+
+/// Attacks whose progress can be saved in a `Checkpoint` and continued later.
+///
+/// A run continued from a checkpoint draws exactly the information sets the interrupted run
+/// would have drawn next, so no work is repeated and iteration counts stay cumulative.
+pub trait Resumable: Attack {
+    /// Parameters that must match for a checkpoint to be resumed (everything except
+    /// `max_iters` and `seed`).
+    fn config(&self) -> String;
+
+    /// Run from `checkpoint` (use `Checkpoint::new()` to start), updating it at every
+    /// iteration boundary. When `budget` stops the run, `checkpoint.stats.stopped` is set
+    /// and the checkpoint can be saved and resumed.
+    fn resume(
+        &self,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+        checkpoint: &mut Checkpoint,
+    ) -> Result<Option<HqcGf2>, AttackError>;
+}
+
+/// `Attack::solve_within` for a `Resumable` attack: a resume from a fresh checkpoint.
+pub(crate) fn solve_fresh<A: Resumable + ?Sized>(
+    attack: &A,
+    n: usize,
+    w: usize,
+    h: &HqcGf2,
+    s: &HqcGf2,
+    budget: &AttackBudget,
+    stats: &mut AttackStats,
+) -> Result<Option<HqcGf2>, AttackError> {
+    let mut checkpoint = Checkpoint {
+        stats: std::mem::take(stats),
+        ..Checkpoint::default()
+    };
+    let result = attack.resume(n, w, h, s, budget, &mut checkpoint);
+    *stats = checkpoint.stats;
+    result
+}
@@ -32,4 +32,34 @@ pub enum AttackError {
     Aborted(String),
     #[error("invalid parameter: {0}")]
     InvalidParameter(String),
-}
\ No newline at end of file
+}
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    #[error("checkpoint I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("malformed checkpoint: {0}")]
+    Malformed(String),
+}
+#[derive(Debug, thiserror::Error)]
+pub enum ExperimentError {
+    #[error("keygen failed: {0}")]
+    Keygen(#[from] HqcKeygenError),
+    #[error("writing experiment rows failed: {0}")]
+    Io(#[from] std::io::Error),
+}
+#[derive(Debug, thiserror::Error)]
+pub enum FitError {
+    #[error("not enough samples to fit: need more than {needed}, got {got}")]
+    NotEnoughSamples { needed: usize, got: usize },
+    #[error("samples do not determine the model (too few distinct w)")]
+    Degenerate,
+}
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SolutionError {
+    #[error("{name} has wrong length (expected {expected}, got {got})")]
+    WrongLength { name: &'static str, expected: usize, got: usize },
+    #[error("{name} has wrong weight (expected {expected}, got {got})")]
+    WrongWeight { name: &'static str, expected: usize, got: usize },
+    #[error("key equation check failed: s != x ⊕ h·y")]
+    EquationFailed,
+}
@@ -0,0 +1,380 @@
+//! Asymptotic-style cost estimates for ISD algorithms, in log2.
+//!
+//! HQC key recovery is treated as syndrome decoding in a random `[2n, n]` code with error
+//! weight `2w` (the `w`/`w` split of `(y, x)` is ignored). Conventions:
+//! - one Gaussian elimination of the `(N - K) x N` parity-check matrix costs `(N - K)^2 · N`
+//!   bit operations;
+//! - every list element built or merged, and every candidate checked, costs one length-N
+//!   vector addition (`N` bit operations);
+//! - memory is the largest list times `N` bits, or the matrix if that is larger.
+//!
+//! Where an algorithm has internal parameters (p, l, ε), `estimate` searches a grid and
+//! returns the parameters with the lowest time.
+
+use super::params::HqcExperimentParams;
+
+pub mod quantum;
+
+/// ISD algorithms covered by the estimator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IsdAlgorithm {
+    Prange,
+    Stern,
+    Dumer,
+    Mmt,
+    /// BJMM with a depth-2 or depth-3 merge tree, whichever is cheaper.
+    Bjmm,
+}
+
+impl IsdAlgorithm {
+    pub const ALL: [IsdAlgorithm; 5] = [
+        IsdAlgorithm::Prange,
+        IsdAlgorithm::Stern,
+        IsdAlgorithm::Dumer,
+        IsdAlgorithm::Mmt,
+        IsdAlgorithm::Bjmm,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IsdAlgorithm::Prange => "Prange",
+            IsdAlgorithm::Stern => "Stern",
+            IsdAlgorithm::Dumer => "Dumer",
+            IsdAlgorithm::Mmt => "MMT",
+            IsdAlgorithm::Bjmm => "BJMM",
+        }
+    }
+}
+
+/// Internal parameters chosen by the search. Unused fields are zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IsdCostParams {
+    /// Weight on the information set (Stern/Dumer: per half).
+    pub p: usize,
+    pub l: usize,
+    /// ε per representation level (BJMM only).
+    pub eps: [usize; 2],
+    /// Merge-tree depth (BJMM only).
+    pub depth: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostEstimate {
+    pub algorithm: IsdAlgorithm,
+    /// log2 of the expected number of bit operations.
+    pub time_log2: f64,
+    /// log2 of the memory in bits.
+    pub memory_log2: f64,
+    /// log2 of the expected number of iterations (eliminations).
+    pub iterations_log2: f64,
+    pub params: IsdCostParams,
+}
+
+/// Syndrome decoding instance: find e of weight `weight` with `H·e = s`, H is
+/// `(code_len - dim) x code_len`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SdCostModel {
+    pub code_len: usize,
+    pub dim: usize,
+    pub weight: usize,
+}
+
+impl SdCostModel {
+    pub fn new(code_len: usize, dim: usize, weight: usize) -> Self {
+        assert!(dim <= code_len && weight <= code_len, "invalid [n, k, w]");
+        Self { code_len, dim, weight }
+    }
+
+    /// HQC key recovery `x + h·y = s`: a `[2n, n]` code with error weight `2w`.
+    pub fn hqc(n: usize, w: usize) -> Self {
+        Self::new(2 * n, n, 2 * w)
+    }
+
+    fn redundancy(&self) -> usize {
+        self.code_len - self.dim
+    }
+
+    /// log2 of the cost of one elimination.
+    fn gauss_log2(&self) -> f64 {
+        let r = self.redundancy().max(1) as f64;
+        2.0 * r.log2() + (self.code_len as f64).log2()
+    }
+
+    /// log2 of the expected number of solutions beyond the planted one (never negative).
+    fn extra_solutions_log2(&self) -> f64 {
+        (log2_binomial(self.code_len, self.weight) - self.redundancy() as f64).max(0.0)
+    }
+
+    fn vec_log2(&self) -> f64 {
+        (self.code_len as f64).log2()
+    }
+
+    fn matrix_log2(&self) -> f64 {
+        (self.redundancy().max(1) as f64).log2() + self.vec_log2()
+    }
+
+    /// Turn a per-iteration cost and success probability into an estimate.
+    fn finish(
+        &self,
+        algorithm: IsdAlgorithm,
+        iter_log2: f64,
+        success_log2: f64,
+        list_log2: f64,
+        params: IsdCostParams,
+    ) -> CostEstimate {
+        let iterations_log2 = (-success_log2 - self.extra_solutions_log2()).max(0.0);
+        CostEstimate {
+            algorithm,
+            time_log2: iter_log2 + iterations_log2,
+            memory_log2: (list_log2 + self.vec_log2()).max(self.matrix_log2()),
+            iterations_log2,
+            params,
+        }
+    }
+
+    pub fn estimate(&self, algorithm: IsdAlgorithm) -> CostEstimate {
+        match algorithm {
+            IsdAlgorithm::Prange => self.prange(),
+            IsdAlgorithm::Stern => self.best(|l, p| self.stern(l, p), 1),
+            IsdAlgorithm::Dumer => self.best(|l, p| self.dumer(l, p), 1),
+            IsdAlgorithm::Mmt => self.best(|l, p| self.mmt(l, p), 4),
+            IsdAlgorithm::Bjmm => self.best(|l, p| self.bjmm(l, p), 2),
+        }
+    }
+
+    pub fn estimate_all(&self) -> Vec<CostEstimate> {
+        IsdAlgorithm::ALL.iter().map(|&a| self.estimate(a)).collect()
+    }
+
+    /// Grid search over l and p (p in steps of `p_step`), keeping the lowest time.
+    fn best(
+        &self,
+        f: impl Fn(usize, usize) -> Option<CostEstimate>,
+        p_step: usize,
+    ) -> CostEstimate {
+        let r = self.redundancy();
+        let l_max = r.min(MAX_L);
+        let p_max = self.weight.min(MAX_P);
+        let mut best: Option<CostEstimate> = None;
+        for l in 0..=l_max {
+            for p in (0..=p_max).step_by(p_step) {
+                keep_best(&mut best, f(l, p));
+            }
+        }
+        best.unwrap_or_else(|| self.prange())
+    }
+
+    fn prange(&self) -> CostEstimate {
+        let (n, r, w) = (self.code_len, self.redundancy(), self.weight);
+        let success = log2_binomial(r, w) - log2_binomial(n, w);
+        self.finish(IsdAlgorithm::Prange, self.gauss_log2(), success, 0.0, IsdCostParams::default())
+    }
+
+    /// Stern: p errors in each half of the information set, none in the l window columns.
+    fn stern(&self, l: usize, p: usize) -> Option<CostEstimate> {
+        let (n, k, w) = (self.code_len, self.dim, self.weight);
+        let r = self.redundancy();
+        if l > r || 2 * p > w || w - 2 * p > r - l {
+            return None;
+        }
+        let half = log2_binomial(k / 2, p);
+        let success = 2.0 * half + log2_binomial(r - l, w - 2 * p) - log2_binomial(n, w);
+        let candidates = 2.0 * half - l as f64;
+        let iter = log2_sum(&[self.gauss_log2(), 1.0 + half + self.vec_log2(), candidates + self.vec_log2()]);
+        let params = IsdCostParams { p, l, ..Default::default() };
+        Some(self.finish(IsdAlgorithm::Stern, iter, success, half, params))
+    }
+
+    /// Dumer: p errors in each half of the information set plus the l window columns.
+    fn dumer(&self, l: usize, p: usize) -> Option<CostEstimate> {
+        let (n, k, w) = (self.code_len, self.dim, self.weight);
+        let r = self.redundancy();
+        if l > r || 2 * p > w || w - 2 * p > r - l {
+            return None;
+        }
+        let half = log2_binomial((k + l) / 2, p);
+        let success = 2.0 * half + log2_binomial(r - l, w - 2 * p) - log2_binomial(n, w);
+        let candidates = 2.0 * half - l as f64;
+        let iter = log2_sum(&[self.gauss_log2(), 1.0 + half + self.vec_log2(), candidates + self.vec_log2()]);
+        let params = IsdCostParams { p, l, ..Default::default() };
+        Some(self.finish(IsdAlgorithm::Dumer, iter, success, half, params))
+    }
+
+    /// MMT: weight p on k + l columns as a disjoint sum of two weight-p/2 vectors, each built
+    /// from two weight-p/4 halves; the first level is filtered on log2 C(p, p/2) bits.
+    fn mmt(&self, l: usize, p: usize) -> Option<CostEstimate> {
+        let (n, k, w) = (self.code_len, self.dim, self.weight);
+        let r = self.redundancy();
+        if p < 4 || !p.is_multiple_of(4) || l > r || p > w || w - p > r - l {
+            return None;
+        }
+        let l1 = log2_binomial(p, p / 2).round();
+        if l1 > l as f64 {
+            return None;
+        }
+        let base = log2_binomial((k + l) / 2, p / 4);
+        let level1 = 2.0 * base - l1;
+        let top = 2.0 * level1 - (l as f64 - l1);
+        let success = log2_binomial(k + l, p) + log2_binomial(r - l, w - p) - log2_binomial(n, w);
+        let v = self.vec_log2();
+        let iter = log2_sum(&[
+            self.gauss_log2(),
+            2.0 + base + v,
+            1.0 + level1 + v,
+            top.max(0.0) + v,
+        ]);
+        let params = IsdCostParams { p, l, ..Default::default() };
+        Some(self.finish(IsdAlgorithm::Mmt, iter, success, base.max(level1), params))
+    }
+
+    /// BJMM, best of depth 2 and depth 3 over ε.
+    fn bjmm(&self, l: usize, p: usize) -> Option<CostEstimate> {
+        let mut best: Option<CostEstimate> = None;
+        for e1 in 0..=MAX_EPS {
+            keep_best(&mut best, self.bjmm_depth2(l, p, e1));
+            for e2 in 0..=MAX_EPS / 2 {
+                keep_best(&mut best, self.bjmm_depth3(l, p, e1, e2));
+            }
+        }
+        best
+    }
+
+    fn bjmm_depth2(&self, l: usize, p: usize, e1: usize) -> Option<CostEstimate> {
+        let (n, k, w) = (self.code_len, self.dim, self.weight);
+        let r = self.redundancy();
+        let p1 = p / 2 + e1;
+        if p < 2 || !p.is_multiple_of(2) || !p1.is_multiple_of(2) || l > r || p > w || w - p > r - l {
+            return None;
+        }
+        let kl = k + l;
+        let r1 = (log2_binomial(p, p / 2) + log2_binomial(kl - p, e1)).floor();
+        if r1 > l as f64 || p1 / 2 > kl / 2 {
+            return None;
+        }
+        let base = log2_binomial(kl / 2, p1 / 2);
+        let level1 = 2.0 * base - r1;
+        let top = 2.0 * level1 - (l as f64 - r1);
+        let success = log2_binomial(kl, p) + log2_binomial(r - l, w - p) - log2_binomial(n, w);
+        let v = self.vec_log2();
+        let iter = log2_sum(&[self.gauss_log2(), 2.0 + base + v, 1.0 + level1 + v, top.max(0.0) + v]);
+        let params = IsdCostParams { p, l, eps: [e1, 0], depth: 2 };
+        Some(self.finish(IsdAlgorithm::Bjmm, iter, success, base.max(level1), params))
+    }
+
+    fn bjmm_depth3(&self, l: usize, p: usize, e1: usize, e2: usize) -> Option<CostEstimate> {
+        let (n, k, w) = (self.code_len, self.dim, self.weight);
+        let r = self.redundancy();
+        let p1 = p / 2 + e1;
+        let p2 = p1 / 2 + e2;
+        if p < 2 || !p.is_multiple_of(2) || !p1.is_multiple_of(2) || !p2.is_multiple_of(2) || l > r || p > w || w - p > r - l {
+            return None;
+        }
+        let kl = k + l;
+        let r1 = (log2_binomial(p, p / 2) + log2_binomial(kl - p, e1)).floor();
+        let r2 = (log2_binomial(p1, p1 / 2) + log2_binomial(kl - p1, e2)).floor().min(r1);
+        if r1 > l as f64 || p2 / 2 > kl / 2 {
+            return None;
+        }
+        let base = log2_binomial(kl / 2, p2 / 2);
+        let level2 = 2.0 * base - r2;
+        let level1 = 2.0 * level2 - (r1 - r2);
+        let top = 2.0 * level1 - (l as f64 - r1);
+        let success = log2_binomial(kl, p) + log2_binomial(r - l, w - p) - log2_binomial(n, w);
+        let v = self.vec_log2();
+        let iter = log2_sum(&[
+            self.gauss_log2(),
+            3.0 + base + v,
+            2.0 + level2 + v,
+            1.0 + level1 + v,
+            top.max(0.0) + v,
+        ]);
+        let memory = base.max(level2).max(level1);
+        let params = IsdCostParams { p, l, eps: [e1, e2], depth: 3 };
+        Some(self.finish(IsdAlgorithm::Bjmm, iter, success, memory, params))
+    }
+}
+
+fn keep_best(best: &mut Option<CostEstimate>, candidate: Option<CostEstimate>) {
+    if let Some(c) = candidate
+        && best.is_none_or(|b| c.time_log2 < b.time_log2)
+    {
+        *best = Some(c);
+    }
+}
+
+/// Search bounds for the internal parameters.
+const MAX_L: usize = 160;
+const MAX_P: usize = 40;
+const MAX_EPS: usize = 8;
+
+/// Estimate one algorithm on the HQC key-recovery instance for `(n, w)`.
+pub fn estimate(algorithm: IsdAlgorithm, n: usize, w: usize) -> CostEstimate {
+    SdCostModel::hqc(n, w).estimate(algorithm)
+}
+
+/// Estimate every algorithm on the HQC key-recovery instance for `params`.
+pub fn estimate_all(params: &HqcExperimentParams) -> Vec<CostEstimate> {
+    SdCostModel::hqc(params.n, params.w).estimate_all()
+}
+
+/// Classical gate counts (log2) of AES-128/192/256 key search, NIST categories 1, 3 and 5.
+pub const NIST_CLASSICAL_LOG2: [(u8, f64); 3] = [(1, 143.0), (3, 207.0), (5, 272.0)];
+
+/// Highest NIST category (1, 3 or 5) whose classical threshold `time_log2` reaches, 0 if none.
+pub fn nist_category(time_log2: f64) -> u8 {
+    NIST_CLASSICAL_LOG2
+        .iter()
+        .filter(|&&(_, t)| time_log2 >= t)
+        .map(|&(c, _)| c)
+        .max()
+        .unwrap_or(0)
+}
+
+/// log2 of the binomial coefficient C(n, k). Exact summation for small k, log-gamma otherwise.
+pub fn log2_binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    if k <= 64 {
+        return (0..k).map(|i| ((n - i) as f64 / (i + 1) as f64).log2()).sum();
+    }
+    (ln_gamma(n as f64 + 1.0) - ln_gamma(k as f64 + 1.0) - ln_gamma((n - k) as f64 + 1.0))
+        / std::f64::consts::LN_2
+}
+
+/// log2(sum 2^x_i).
+pub fn log2_sum(xs: &[f64]) -> f64 {
+    let m = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if m == f64::NEG_INFINITY {
+        return m;
+    }
+    m + xs.iter().map(|&x| (x - m).exp2()).sum::<f64>().log2()
+}
+
+/// ln Γ(x) for x > 0 (Lanczos, g = 7).
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEF: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let a = COEF[1..]
+        .iter()
+        .enumerate()
+        .fold(COEF[0], |acc, (i, &c)| acc + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
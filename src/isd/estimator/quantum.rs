@@ -0,0 +1,195 @@
+//! Grover-based quantum ISD estimates, in log2.
+//!
+//! Same syndrome decoding view as the classical estimator (`[2n, n]`, weight `2w`). Conventions:
+//! - Grover over a search space in which a fraction `P` is good takes `(π/4)·P^(-1/2)`
+//!   iterations;
+//! - one reversible elimination costs `(N - K)^2 · N` gates and has depth `(N - K) · log2 N`;
+//! - under a depth limit MAXDEPTH the search space is split over parallel Grover instances,
+//!   each of which fits in MAXDEPTH, so the gate count becomes `(π/4)^2 · d · g / (P · MAXDEPTH)`
+//!   for iteration depth d and gate count g;
+//! - qubits count the matrix plus, for Stern, the QRAM holding one list.
+
+use super::{log2_binomial, log2_sum, IsdCostParams, SdCostModel, MAX_P};
+use crate::hqc::params::HqcPkeParams;
+use crate::isd::params::HqcExperimentParams;
+
+/// MAXDEPTH values (log2) suggested in the NIST call for proposals.
+pub const NIST_MAXDEPTHS_LOG2: [f64; 3] = [40.0, 64.0, 96.0];
+
+/// Quantum gate counts of AES-128/192/256 key search, as log2(gates · MAXDEPTH).
+pub const NIST_QUANTUM_LOG2: [(u8, f64); 3] = [(1, 170.0), (3, 233.0), (5, 298.0)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QuantumIsdAlgorithm {
+    /// Grover search over information sets (Bernstein).
+    Prange,
+    /// Grover over information sets, with the Stern collision found by an inner Grover search
+    /// against a QRAM table of the left list.
+    Stern,
+}
+
+impl QuantumIsdAlgorithm {
+    pub const ALL: [QuantumIsdAlgorithm; 2] = [QuantumIsdAlgorithm::Prange, QuantumIsdAlgorithm::Stern];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            QuantumIsdAlgorithm::Prange => "Quantum Prange",
+            QuantumIsdAlgorithm::Stern => "Quantum Stern",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuantumCostEstimate {
+    pub algorithm: QuantumIsdAlgorithm,
+    /// log2 of the gate count without a depth limit.
+    pub gates_log2: f64,
+    /// log2 of the circuit depth without a depth limit.
+    pub depth_log2: f64,
+    /// The depth limit applied to `maxdepth_gates_log2`.
+    pub maxdepth_log2: f64,
+    /// log2 of the total gate count when every circuit has depth at most MAXDEPTH.
+    pub maxdepth_gates_log2: f64,
+    /// log2 of the number of (logical) qubits, including QRAM.
+    pub qubits_log2: f64,
+    pub params: IsdCostParams,
+}
+
+impl QuantumCostEstimate {
+    /// Highest NIST category met under the estimate's MAXDEPTH.
+    pub fn nist_category(&self) -> u8 {
+        nist_category(self.maxdepth_gates_log2, self.maxdepth_log2)
+    }
+}
+
+/// Highest NIST category (1, 3 or 5) whose quantum threshold `gates_log2` reaches under
+/// the depth limit `maxdepth_log2`, 0 if none.
+pub fn nist_category(gates_log2: f64, maxdepth_log2: f64) -> u8 {
+    NIST_QUANTUM_LOG2
+        .iter()
+        .filter(|&&(_, t)| gates_log2 >= t - maxdepth_log2)
+        .map(|&(c, _)| c)
+        .max()
+        .unwrap_or(0)
+}
+
+const LOG2_PI_4: f64 = -0.348_503_870_527_681_3;
+
+impl SdCostModel {
+    /// Estimate `algorithm` with internal parameters chosen to minimise the MAXDEPTH gate count.
+    pub fn quantum_estimate(
+        &self,
+        algorithm: QuantumIsdAlgorithm,
+        maxdepth_log2: f64,
+    ) -> QuantumCostEstimate {
+        match algorithm {
+            QuantumIsdAlgorithm::Prange => self.quantum_prange(maxdepth_log2),
+            QuantumIsdAlgorithm::Stern => {
+                let mut best = self.quantum_prange(maxdepth_log2);
+                best.algorithm = QuantumIsdAlgorithm::Stern;
+                for p in 1..=(self.weight / 2).min(MAX_P) {
+                    if let Some(c) = self.quantum_stern(p, maxdepth_log2)
+                        && c.maxdepth_gates_log2 < best.maxdepth_gates_log2
+                    {
+                        best = c;
+                    }
+                }
+                best
+            }
+        }
+    }
+
+    pub fn quantum_estimate_all(&self, maxdepth_log2: f64) -> Vec<QuantumCostEstimate> {
+        QuantumIsdAlgorithm::ALL
+            .iter()
+            .map(|&a| self.quantum_estimate(a, maxdepth_log2))
+            .collect()
+    }
+
+    fn gauss_depth_log2(&self) -> f64 {
+        (self.redundancy().max(1) as f64).log2() + self.vec_log2().log2()
+    }
+
+    /// Amplify a search with success probability `2^success_log2` per iteration, whose
+    /// iteration circuit has `gates_log2` gates and depth `depth_log2`.
+    #[allow(clippy::too_many_arguments)]
+    fn grover(
+        &self,
+        algorithm: QuantumIsdAlgorithm,
+        success_log2: f64,
+        gates_log2: f64,
+        depth_log2: f64,
+        qubits_log2: f64,
+        maxdepth_log2: f64,
+        params: IsdCostParams,
+    ) -> QuantumCostEstimate {
+        let space = (-success_log2 - self.extra_solutions_log2()).max(0.0);
+        let iterations = (LOG2_PI_4 + space / 2.0).max(0.0);
+        let total_depth = iterations + depth_log2;
+        let maxdepth_gates = if total_depth <= maxdepth_log2 {
+            iterations + gates_log2
+        } else {
+            2.0 * LOG2_PI_4 + space + depth_log2 + gates_log2 - maxdepth_log2
+        };
+        QuantumCostEstimate {
+            algorithm,
+            gates_log2: iterations + gates_log2,
+            depth_log2: total_depth,
+            maxdepth_log2,
+            maxdepth_gates_log2: maxdepth_gates,
+            qubits_log2,
+            params,
+        }
+    }
+
+    fn quantum_prange(&self, maxdepth_log2: f64) -> QuantumCostEstimate {
+        let (n, r, w) = (self.code_len, self.redundancy(), self.weight);
+        let success = log2_binomial(r, w) - log2_binomial(n, w);
+        self.grover(
+            QuantumIsdAlgorithm::Prange,
+            success,
+            self.gauss_log2(),
+            self.gauss_depth_log2(),
+            self.matrix_log2(),
+            maxdepth_log2,
+            IsdCostParams::default(),
+        )
+    }
+
+    /// Per iteration: elimination, building the left list of size L into QRAM (sorting
+    /// network of depth log2(L)^2), then an inner Grover search of `(π/4)·sqrt(L)` lookups
+    /// over the right list. The inner search checks full syndromes, so no window is needed
+    /// (l = 0).
+    fn quantum_stern(&self, p: usize, maxdepth_log2: f64) -> Option<QuantumCostEstimate> {
+        let (n, k, w) = (self.code_len, self.dim, self.weight);
+        let r = self.redundancy();
+        if 2 * p > w || w - 2 * p > r {
+            return None;
+        }
+        let half = log2_binomial(k / 2, p);
+        let success = 2.0 * half + log2_binomial(r, w - 2 * p) - log2_binomial(n, w);
+        let v = self.vec_log2();
+        let inner = LOG2_PI_4 + half / 2.0;
+        let gates = log2_sum(&[self.gauss_log2(), half + v, inner + v]);
+        let sort_depth = 2.0 * half.max(1.0).log2();
+        let depth = log2_sum(&[self.gauss_depth_log2(), sort_depth, inner + v.log2()]);
+        let qubits = (half + v).max(self.matrix_log2());
+        let params = IsdCostParams { p, ..Default::default() };
+        Some(self.grover(QuantumIsdAlgorithm::Stern, success, gates, depth, qubits, maxdepth_log2, params))
+    }
+}
+
+/// Quantum estimates for the HQC key-recovery instance of `params`.
+pub fn estimate_all(params: &HqcExperimentParams, maxdepth_log2: f64) -> Vec<QuantumCostEstimate> {
+    SdCostModel::hqc(params.n, params.w).quantum_estimate_all(maxdepth_log2)
+}
+
+/// Quantum estimates for key recovery (`x + h·y = s`, weight `W`) of an HQC parameter set.
+pub fn estimate_key_recovery<P: HqcPkeParams>(maxdepth_log2: f64) -> Vec<QuantumCostEstimate> {
+    SdCostModel::hqc(P::N, P::W).quantum_estimate_all(maxdepth_log2)
+}
+
+/// Quantum estimates for recovering `(r1, r2)` from `u = r1 + h·r2` (weight `W_R`).
+pub fn estimate_message_recovery<P: HqcPkeParams>(maxdepth_log2: f64) -> Vec<QuantumCostEstimate> {
+    SdCostModel::hqc(P::N, P::W_R).quantum_estimate_all(maxdepth_log2)
+}
@@ -0,0 +1,342 @@
+use super::attack::Attack;
+use super::budget::{AttackBudget, StopReason};
+use super::error::ExperimentError;
+use super::params::HqcExperimentParams;
+use super::verify::{Verdict, classify};
+use crate::hqc::hash::xof::Shake256Xof;
+use crate::hqc::types::Seed32;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::time::Duration;
+
+/// The `(n, w)` points of a sweep and how many key pairs to draw at each.
+///
+/// The keygen seed and attack seed of every run are derived from `master_seed`, the point and
+/// the key index, so a row can be reproduced on its own without replaying the whole sweep.
+#[derive(Clone, Debug)]
+pub struct ExperimentGrid {
+    pub points: Vec<HqcExperimentParams>,
+    pub seeds_per_point: usize,
+    pub master_seed: u64,
+}
+
+/// One key pair of the grid: where it sits and the seeds it is run with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridRun {
+    pub params: HqcExperimentParams,
+    /// Index of the key pair at this point, in `0..seeds_per_point`.
+    pub key_index: usize,
+    pub keygen_seed: Seed32,
+    pub attack_seed: u64,
+}
+
+impl ExperimentGrid {
+    pub fn new(seeds_per_point: usize, master_seed: u64) -> Self {
+        Self {
+            points: Vec::new(),
+            seeds_per_point,
+            master_seed,
+        }
+    }
+
+    pub fn with_point(mut self, n: usize, w: usize) -> Self {
+        self.points.push(HqcExperimentParams::new(n, w));
+        self
+    }
+
+    /// Add `family(w)` for every w, e.g. `HqcExperimentParams::sparse_parameters_hqc_1`.
+    pub fn with_family(
+        mut self,
+        family: fn(usize) -> HqcExperimentParams,
+        ws: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        self.points.extend(ws.into_iter().map(family));
+        self
+    }
+
+    /// Every key pair of the sweep, point by point.
+    pub fn runs(&self) -> Vec<GridRun> {
+        let mut runs = Vec::with_capacity(self.points.len() * self.seeds_per_point);
+        for &params in &self.points {
+            for key_index in 0..self.seeds_per_point {
+                runs.push(self.run_at(params, key_index));
+            }
+        }
+        runs
+    }
+
+    /// Seeds of key pair `key_index` at `params`.
+    pub fn run_at(&self, params: HqcExperimentParams, key_index: usize) -> GridRun {
+        let mut input = Vec::with_capacity(40);
+        input.extend_from_slice(b"isd4hqc-experiment");
+        input.extend_from_slice(&self.master_seed.to_le_bytes());
+        input.extend_from_slice(&(params.n as u64).to_le_bytes());
+        input.extend_from_slice(&(params.w as u64).to_le_bytes());
+        input.extend_from_slice(&(key_index as u64).to_le_bytes());
+        let bytes = Shake256Xof::new(&input).get_bytes(40);
+        let mut keygen_seed = [0u8; 32];
+        keygen_seed.copy_from_slice(&bytes[..32]);
+        let mut attack_seed = [0u8; 8];
+        attack_seed.copy_from_slice(&bytes[32..]);
+        GridRun {
+            params,
+            key_index,
+            keygen_seed,
+            attack_seed: u64::from_le_bytes(attack_seed),
+        }
+    }
+}
+
+/// An attack of the sweep, built afresh for every run from that run's attack seed.
+pub struct AttackSpec {
+    pub label: String,
+    make: Box<dyn Fn(u64) -> Box<dyn Attack>>,
+}
+
+impl AttackSpec {
+    pub fn new<A, F>(label: impl Into<String>, make: F) -> Self
+    where
+        A: Attack + 'static,
+        F: Fn(u64) -> A + 'static,
+    {
+        Self {
+            label: label.into(),
+            make: Box::new(move |seed| Box::new(make(seed))),
+        }
+    }
+
+    pub fn build(&self, seed: u64) -> Box<dyn Attack> {
+        (self.make)(seed)
+    }
+}
+
+/// Result of one attack on one key pair.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExperimentRow {
+    pub attack: String,
+    pub n: usize,
+    pub w: usize,
+    pub key_index: usize,
+    pub keygen_seed: Seed32,
+    /// RNG seed the attack reported, None for deterministic attacks.
+    pub attack_seed: Option<u64>,
+    /// A y with `wt(y) = wt(s + h·y) = w` was returned.
+    pub success: bool,
+    /// The returned y is the planted one.
+    pub planted: bool,
+    pub iterations: u64,
+    pub eliminations: u64,
+    pub candidate_checks: u64,
+    pub max_table_len: usize,
+    pub wall_time: Duration,
+    pub stopped: Option<StopReason>,
+    /// The attack rejected the instance (e.g. a parameter too large for this n).
+    pub error: Option<String>,
+}
+
+/// Output format of `Experiment::run_to`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowFormat {
+    /// One JSON object per line.
+    Jsonl,
+    /// A header line, then one comma-separated line per row.
+    Csv,
+}
+
+impl ExperimentRow {
+    pub const CSV_HEADER: &'static str = "attack,n,w,key_index,keygen_seed,attack_seed,success,planted,iterations,eliminations,candidate_checks,max_table_len,time_ms,stopped,error";
+
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "{{\"attack\":{},\"n\":{},\"w\":{},\"key_index\":{},\"keygen_seed\":\"{}\",\"attack_seed\":{},\"success\":{},\"planted\":{},\"iterations\":{},\"eliminations\":{},\"candidate_checks\":{},\"max_table_len\":{},\"time_ms\":{:.3},\"stopped\":{},\"error\":{}}}",
+            json_string(&self.attack),
+            self.n,
+            self.w,
+            self.key_index,
+            hex::encode(self.keygen_seed),
+            self.attack_seed.map_or("null".to_string(), |s| s.to_string()),
+            self.success,
+            self.planted,
+            self.iterations,
+            self.eliminations,
+            self.candidate_checks,
+            self.max_table_len,
+            self.time_ms(),
+            self.stopped.map_or("null".to_string(), |r| json_string(&format!("{:?}", r))),
+            self.error.as_deref().map_or("null".to_string(), json_string),
+        );
+        out
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{:.3},{},{}",
+            csv_field(&self.attack),
+            self.n,
+            self.w,
+            self.key_index,
+            hex::encode(self.keygen_seed),
+            self.attack_seed.map_or(String::new(), |s| s.to_string()),
+            self.success,
+            self.planted,
+            self.iterations,
+            self.eliminations,
+            self.candidate_checks,
+            self.max_table_len,
+            self.time_ms(),
+            self.stopped.map_or(String::new(), |r| format!("{:?}", r)),
+            self.error.as_deref().map_or(String::new(), csv_field),
+        );
+        out
+    }
+
+    fn time_ms(&self) -> f64 {
+        self.wall_time.as_secs_f64() * 1e3
+    }
+}
+
+fn json_string(v: &str) -> String {
+    let mut out = String::with_capacity(v.len() + 2);
+    out.push('"');
+    for c in v.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(v: &str) -> String {
+    if v.contains([',', '"', '\n']) {
+        format!("\"{}\"", v.replace('"', "\"\""))
+    } else {
+        v.to_string()
+    }
+}
+
+/// A sweep: every attack on every key pair of the grid.
+pub struct Experiment {
+    pub grid: ExperimentGrid,
+    pub attacks: Vec<AttackSpec>,
+    /// Limits applied to each run; a deadline here is shared by the whole sweep.
+    pub budget: AttackBudget,
+    /// Wall-time limit of each run, counted from its start.
+    pub time_limit: Option<Duration>,
+}
+
+impl Experiment {
+    pub fn new(grid: ExperimentGrid) -> Self {
+        Self {
+            grid,
+            attacks: Vec::new(),
+            budget: AttackBudget::unlimited(),
+            time_limit: None,
+        }
+    }
+
+    pub fn with_attack<A, F>(mut self, label: impl Into<String>, make: F) -> Self
+    where
+        A: Attack + 'static,
+        F: Fn(u64) -> A + 'static,
+    {
+        self.attacks.push(AttackSpec::new(label, make));
+        self
+    }
+
+    pub fn with_budget(mut self, budget: AttackBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Run the sweep, handing each row to `sink` as soon as it is done. Attack errors are
+    /// recorded in the row; keygen and sink errors stop the sweep.
+    pub fn run(
+        &self,
+        mut sink: impl FnMut(ExperimentRow) -> Result<(), ExperimentError>,
+    ) -> Result<(), ExperimentError> {
+        for run in self.grid.runs() {
+            let params = run.params;
+            let instance = params.keygen(run.keygen_seed)?;
+            let (h, s) = instance.get_public_key();
+            let (y, _) = instance.get_secret_key();
+            for spec in &self.attacks {
+                let budget = match self.time_limit {
+                    Some(limit) => self.budget.clone().with_time_limit(limit),
+                    None => self.budget.clone(),
+                };
+                let attack = spec.build(run.attack_seed);
+                let mut row = ExperimentRow {
+                    attack: spec.label.clone(),
+                    n: params.n,
+                    w: params.w,
+                    key_index: run.key_index,
+                    keygen_seed: run.keygen_seed,
+                    attack_seed: None,
+                    success: false,
+                    planted: false,
+                    iterations: 0,
+                    eliminations: 0,
+                    candidate_checks: 0,
+                    max_table_len: 0,
+                    wall_time: Duration::ZERO,
+                    stopped: None,
+                    error: None,
+                };
+                match attack.report_within(params.n, params.w, h, s, &budget) {
+                    Ok(report) => {
+                        let st = &report.stats;
+                        if let Some(found) = &report.solution {
+                            let verdict = classify(params.w, h, s, found, y);
+                            row.success = verdict.is_valid();
+                            row.planted = verdict == Verdict::Planted;
+                        }
+                        row.attack_seed = st.seed;
+                        row.iterations = st.iterations;
+                        row.eliminations = st.eliminations;
+                        row.candidate_checks = st.candidate_checks;
+                        row.max_table_len = st.max_table_len;
+                        row.wall_time = report.wall_time;
+                        row.stopped = st.stopped;
+                    }
+                    Err(e) => row.error = Some(e.to_string()),
+                }
+                sink(row)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the sweep and write one line per row to `out`. Returns the number of rows.
+    pub fn run_to(&self, out: &mut impl Write, format: RowFormat) -> Result<usize, ExperimentError> {
+        if format == RowFormat::Csv {
+            writeln!(out, "{}", ExperimentRow::CSV_HEADER)?;
+        }
+        let mut rows = 0;
+        self.run(|row| {
+            match format {
+                RowFormat::Jsonl => writeln!(out, "{}", row.to_json())?,
+                RowFormat::Csv => writeln!(out, "{}", row.to_csv())?,
+            }
+            out.flush()?;
+            rows += 1;
+            Ok(())
+        })?;
+        Ok(rows)
+    }
+}
@@ -0,0 +1,341 @@
+//! Extrapolation of measured attack work along the `sparse_parameters_hqc_*` families.
+//!
+//! Within a family n/w is constant, so ISD work grows exponentially in w: log2(work) is
+//! fitted by least squares as `a + b·w` (or `a + b·w + c·log2 w` to absorb polynomial
+//! factors), and the fit is evaluated at the w of `hqc_1/3/5`. Intervals are Student-t
+//! confidence intervals for the fitted mean, so they only cover the statistical error of
+//! the fit, not a change of regime between the measured w and the target.
+
+use super::error::FitError;
+use super::estimator::ln_gamma;
+use super::experiment::ExperimentRow;
+use super::params::{HqcExperimentParams, HqcFamily};
+
+/// Which counter of an `ExperimentRow` counts as work.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkMeasure {
+    Iterations,
+    Eliminations,
+    CandidateChecks,
+    /// Wall time in seconds.
+    WallTime,
+}
+
+impl WorkMeasure {
+    pub fn of(self, row: &ExperimentRow) -> f64 {
+        match self {
+            WorkMeasure::Iterations => row.iterations as f64,
+            WorkMeasure::Eliminations => row.eliminations as f64,
+            WorkMeasure::CandidateChecks => row.candidate_checks as f64,
+            WorkMeasure::WallTime => row.wall_time.as_secs_f64(),
+        }
+    }
+}
+
+/// One observation: log2 of the work measured at weight w.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sample {
+    pub w: usize,
+    pub log2_work: f64,
+}
+
+/// One sample per successful run of `attack` on a member of `family`.
+///
+/// Runs that failed or were stopped by their budget are dropped, which biases the fit low
+/// when budgets are tight; give the sweep enough budget for every run to finish.
+pub fn samples(
+    rows: &[ExperimentRow],
+    attack: &str,
+    family: HqcFamily,
+    measure: WorkMeasure,
+) -> Vec<Sample> {
+    rows.iter()
+        .filter(|r| r.attack == attack && r.success && family.contains(r.n, r.w))
+        .map(|r| (r.w, measure.of(r)))
+        .filter(|&(_, work)| work > 0.0)
+        .map(|(w, work)| Sample { w, log2_work: work.log2() })
+        .collect()
+}
+
+/// One sample per weight: log2 of the mean work over the successful runs at that w.
+///
+/// Fitting per-run samples estimates E[log2 work], which for a geometric number of
+/// iterations sits below log2 E[work]; averaging first estimates the latter.
+pub fn mean_samples(
+    rows: &[ExperimentRow],
+    attack: &str,
+    family: HqcFamily,
+    measure: WorkMeasure,
+) -> Vec<Sample> {
+    let mut sums: Vec<(usize, f64, usize)> = Vec::new();
+    for r in rows {
+        if r.attack != attack || !r.success || !family.contains(r.n, r.w) {
+            continue;
+        }
+        match sums.iter_mut().find(|(w, _, _)| *w == r.w) {
+            Some((_, sum, count)) => {
+                *sum += measure.of(r);
+                *count += 1;
+            }
+            None => sums.push((r.w, measure.of(r), 1)),
+        }
+    }
+    sums.sort_by_key(|&(w, _, _)| w);
+    sums.into_iter()
+        .filter(|&(_, sum, _)| sum > 0.0)
+        .map(|(w, sum, count)| Sample { w, log2_work: (sum / count as f64).log2() })
+        .collect()
+}
+
+/// Shape of log2(work) as a function of w.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FitModel {
+    /// `a + b·w`.
+    Linear,
+    /// `a + b·w + c·log2 w`.
+    LinearLog,
+}
+
+impl FitModel {
+    fn regressors(self, w: usize) -> Vec<f64> {
+        let w = w as f64;
+        match self {
+            FitModel::Linear => vec![1.0, w],
+            FitModel::LinearLog => vec![1.0, w, w.log2()],
+        }
+    }
+}
+
+/// Least-squares fit of log2(work) against w.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fit {
+    pub model: FitModel,
+    /// `[a, b]` or `[a, b, c]`, see `FitModel`.
+    pub coefficients: Vec<f64>,
+    /// Covariance of the coefficients.
+    pub covariance: Vec<Vec<f64>>,
+    /// Residual variance, in bits².
+    pub residual_variance: f64,
+    pub samples: usize,
+    /// Degrees of freedom: samples minus coefficients.
+    pub dof: usize,
+    pub r_squared: f64,
+}
+
+/// The fit evaluated at a parameter set.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Extrapolation {
+    pub params: HqcExperimentParams,
+    pub log2_work: f64,
+    pub lower: f64,
+    pub upper: f64,
+    /// Confidence level of `[lower, upper]`, e.g. 0.95.
+    pub level: f64,
+}
+
+impl Fit {
+    pub fn new(samples: &[Sample], model: FitModel) -> Result<Self, FitError> {
+        let k = model.regressors(1).len();
+        if samples.len() <= k {
+            return Err(FitError::NotEnoughSamples { needed: k, got: samples.len() });
+        }
+        let mut xtx = vec![vec![0.0; k]; k];
+        let mut xty = vec![0.0; k];
+        for sample in samples {
+            let x = model.regressors(sample.w);
+            for i in 0..k {
+                xty[i] += x[i] * sample.log2_work;
+                for j in 0..k {
+                    xtx[i][j] += x[i] * x[j];
+                }
+            }
+        }
+        let inverse = invert(xtx).ok_or(FitError::Degenerate)?;
+        let coefficients: Vec<f64> = inverse
+            .iter()
+            .map(|row| row.iter().zip(&xty).map(|(a, b)| a * b).sum())
+            .collect();
+
+        let mean = samples.iter().map(|s| s.log2_work).sum::<f64>() / samples.len() as f64;
+        let mut rss = 0.0;
+        let mut tss = 0.0;
+        for sample in samples {
+            let fitted = dot(&model.regressors(sample.w), &coefficients);
+            rss += (sample.log2_work - fitted).powi(2);
+            tss += (sample.log2_work - mean).powi(2);
+        }
+        let dof = samples.len() - k;
+        let residual_variance = rss / dof as f64;
+        let covariance = inverse
+            .iter()
+            .map(|row| row.iter().map(|v| v * residual_variance).collect())
+            .collect();
+        Ok(Self {
+            model,
+            coefficients,
+            covariance,
+            residual_variance,
+            samples: samples.len(),
+            dof,
+            r_squared: if tss > 0.0 { 1.0 - rss / tss } else { 1.0 },
+        })
+    }
+
+    /// Bits of work added per unit of w.
+    pub fn slope(&self) -> f64 {
+        self.coefficients[1]
+    }
+
+    /// Fitted log2(work) at weight w.
+    pub fn predict(&self, w: usize) -> f64 {
+        dot(&self.model.regressors(w), &self.coefficients)
+    }
+
+    /// Two-sided `level` confidence interval for the fitted log2(work) at w.
+    pub fn confidence_interval(&self, w: usize, level: f64) -> (f64, f64) {
+        let x = self.model.regressors(w);
+        let variance: f64 = self
+            .covariance
+            .iter()
+            .zip(&x)
+            .map(|(row, xi)| xi * dot(row, &x))
+            .sum();
+        let half = student_t_quantile(0.5 + level / 2.0, self.dof) * variance.max(0.0).sqrt();
+        let mid = self.predict(w);
+        (mid - half, mid + half)
+    }
+
+    pub fn extrapolate(&self, params: HqcExperimentParams, level: f64) -> Extrapolation {
+        let (lower, upper) = self.confidence_interval(params.w, level);
+        Extrapolation {
+            params,
+            log2_work: self.predict(params.w),
+            lower,
+            upper,
+            level,
+        }
+    }
+
+    /// `extrapolate` to the full parameter set of `family`.
+    pub fn extrapolate_family(&self, family: HqcFamily, level: f64) -> Extrapolation {
+        self.extrapolate(family.target(), level)
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Gauss–Jordan inverse with partial pivoting; None if (numerically) singular.
+fn invert(mut a: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let k = a.len();
+    let scale = a.iter().flatten().fold(0.0f64, |m, v| m.max(v.abs()));
+    let mut inv: Vec<Vec<f64>> = (0..k)
+        .map(|i| (0..k).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    for col in 0..k {
+        let pivot = (col..k).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() <= scale * 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+        let p = a[col][col];
+        for j in 0..k {
+            a[col][j] /= p;
+            inv[col][j] /= p;
+        }
+        for i in 0..k {
+            if i != col {
+                let f = a[i][col];
+                for j in 0..k {
+                    a[i][j] -= f * a[col][j];
+                    inv[i][j] -= f * inv[col][j];
+                }
+            }
+        }
+    }
+    Some(inv)
+}
+
+/// Quantile of Student's t distribution with `dof` degrees of freedom, by bisection on the CDF.
+pub fn student_t_quantile(p: f64, dof: usize) -> f64 {
+    assert!(p > 0.0 && p < 1.0 && dof > 0, "need 0 < p < 1 and dof > 0");
+    if p < 0.5 {
+        return -student_t_quantile(1.0 - p, dof);
+    }
+    let mut lo = 0.0;
+    let mut hi = 1.0;
+    while student_t_cdf(hi, dof) < p {
+        hi *= 2.0;
+    }
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if student_t_cdf(mid, dof) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// CDF of Student's t distribution, via the regularized incomplete beta function.
+pub fn student_t_cdf(t: f64, dof: usize) -> f64 {
+    let v = dof as f64;
+    let tail = 0.5 * incomplete_beta(v / 2.0, 0.5, v / (v + t * t));
+    if t >= 0.0 { 1.0 - tail } else { tail }
+}
+
+/// Regularized incomplete beta function I_x(a, b) (continued fraction, Numerical Recipes).
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let ln_front =
+        ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        ln_front.exp() * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - ln_front.exp() * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..300 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        let even = m * (b - m) * x / ((a + m2 - 1.0) * (a + m2));
+        d = 1.0 + even * d;
+        d = if d.abs() < TINY { 1.0 / TINY } else { 1.0 / d };
+        c = 1.0 + even / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        h *= d * c;
+        let odd = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.0));
+        d = 1.0 + odd * d;
+        d = if d.abs() < TINY { 1.0 / TINY } else { 1.0 / d };
+        c = 1.0 + odd / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    h
+}
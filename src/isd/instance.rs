@@ -0,0 +1,334 @@
+use super::attack::{Attack, AttackStats};
+use super::budget::AttackBudget;
+use super::error::AttackError;
+use crate::gf::binary_matrix::BinaryMatrix;
+use crate::gf::gf2::Gf2Construct;
+use crate::hqc::hqcgf2::HqcGf2;
+use crate::hqc::params::HqcPkeParams;
+use crate::hqc::pke::expand_public_key;
+use crate::hqc::types::EkPke;
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+use rand::{Rng, SeedableRng};
+
+/// A binary syndrome-decoding instance: find e with `H·e = s` and the weight structure the
+/// instance asks for.
+///
+/// Errors are `HqcGf2` vectors of length `code_len()`, bit c being the coefficient of
+/// column c of H. Syndromes have length `redundancy()`.
+pub trait SdInstance {
+    /// Columns of H.
+    fn code_len(&self) -> usize;
+    /// Rows of H, i.e. the syndrome length.
+    fn redundancy(&self) -> usize;
+    /// Total weight of a solution.
+    fn weight(&self) -> usize;
+    fn syndrome(&self) -> &HqcGf2;
+    /// Column c of H into `out` (length `redundancy()`); `tmp` is scratch.
+    fn column_into(&self, c: usize, out: &mut HqcGf2, tmp: &mut Vec<u64>);
+
+    /// `H·e`.
+    fn syndrome_of(&self, e: &HqcGf2) -> HqcGf2 {
+        let mut out = HqcGf2::zero_with_len(self.redundancy());
+        let mut col = HqcGf2::zero_with_len(self.redundancy());
+        let mut tmp = vec![0u64; HqcGf2::word_len(self.redundancy())];
+        for c in e.ones_indices() {
+            self.column_into(c, &mut col, &mut tmp);
+            out.xor_in_place(&col);
+        }
+        out
+    }
+
+    /// Constraints on e beyond its total weight, e.g. per-block weights. Defaults to none.
+    fn weight_profile_ok(&self, e: &HqcGf2) -> bool {
+        let _ = e;
+        true
+    }
+
+    /// `wt(e) = weight()`, the weight profile holds and `H·e = s`.
+    fn is_solution(&self, e: &HqcGf2) -> bool {
+        e.n == self.code_len()
+            && e.weight() as usize == self.weight()
+            && self.weight_profile_ok(e)
+            && self.syndrome_of(e) == *self.syndrome()
+    }
+}
+
+/// Attacks that run on any `SdInstance`, returning the full error vector.
+pub trait SdAttack: Attack {
+    fn solve_instance<I: SdInstance + ?Sized>(
+        &self,
+        instance: &I,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<HqcGf2>, AttackError>;
+}
+
+/// Block i (bits `i·n..(i + 1)·n`) of e.
+pub fn block(e: &HqcGf2, i: usize, n: usize) -> HqcGf2 {
+    let mut out = HqcGf2::zero_with_len(n);
+    for c in e.ones_indices() {
+        if c / n == i {
+            out.set(c - i * n);
+        }
+    }
+    out
+}
+
+fn block_weights_match(e: &HqcGf2, n: usize, weights: &[usize]) -> bool {
+    let mut counts = vec![0usize; weights.len()];
+    for c in e.ones_indices() {
+        counts[c / n] += 1;
+    }
+    counts == weights
+}
+
+/// HQC key recovery `x + h·y = s` as the double-circulant code `[h | I]`, e = (y, x) with
+/// `wt(y) = wt(x) = w`. This is the shape `Attack::solve` works on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QcDoubleCirculant {
+    pub h: HqcGf2,
+    pub s: HqcGf2,
+    pub w: usize,
+}
+
+impl QcDoubleCirculant {
+    pub fn new(h: HqcGf2, s: HqcGf2, w: usize) -> Result<Self, AttackError> {
+        if h.n != s.n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h.n != s.n".to_string(),
+            ));
+        }
+        Ok(Self { h, s, w })
+    }
+
+    /// The y half of a solution.
+    pub fn y_of(&self, e: &HqcGf2) -> HqcGf2 {
+        e.truncate(self.h.n)
+    }
+}
+
+/// Key recovery on a real public key (`EkPke` or `EkKem`), h expanded from `seed_ek`.
+impl<P: HqcPkeParams> From<&EkPke<P>> for QcDoubleCirculant {
+    fn from(ek: &EkPke<P>) -> Self {
+        let (h, s) = expand_public_key(ek);
+        Self { h, s, w: P::W }
+    }
+}
+
+impl SdInstance for QcDoubleCirculant {
+    fn code_len(&self) -> usize {
+        2 * self.h.n
+    }
+
+    fn redundancy(&self) -> usize {
+        self.h.n
+    }
+
+    fn weight(&self) -> usize {
+        2 * self.w
+    }
+
+    fn syndrome(&self) -> &HqcGf2 {
+        &self.s
+    }
+
+    fn column_into(&self, c: usize, out: &mut HqcGf2, tmp: &mut Vec<u64>) {
+        let n = self.h.n;
+        if c < n {
+            self.h.rotate_left_into(c, out, tmp);
+        } else {
+            out.clear_all();
+            out.set(c - n);
+        }
+    }
+
+    fn syndrome_of(&self, e: &HqcGf2) -> HqcGf2 {
+        let n = self.h.n;
+        let mut out = block(e, 1, n);
+        out.xor_in_place(&self.h.mul_bitpacked(&block(e, 0, n)));
+        out
+    }
+
+    fn weight_profile_ok(&self, e: &HqcGf2) -> bool {
+        block_weights_match(e, self.h.n, &[self.w, self.w])
+    }
+}
+
+/// The triple-circulant code of an HQC ciphertext,
+///
+/// ```text
+/// [ I  h  0 ] (r1, r2, e) = (u, v)
+/// [ 0  s  I ]
+/// ```
+///
+/// with per-block weights `weights = [wt(r1), wt(r2), wt(e)]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QcTripleCirculant {
+    pub h: HqcGf2,
+    pub s: HqcGf2,
+    pub weights: [usize; 3],
+    /// `(u, v)` as one length-2n vector.
+    syndrome: HqcGf2,
+}
+
+impl QcTripleCirculant {
+    pub fn new(
+        h: HqcGf2,
+        s: HqcGf2,
+        u: &HqcGf2,
+        v: &HqcGf2,
+        weights: [usize; 3],
+    ) -> Result<Self, AttackError> {
+        let n = h.n;
+        if s.n != n || u.n != n || v.n != n {
+            return Err(AttackError::InvalidParameter(
+                "length mismatch: h, s, u and v must have the same length".to_string(),
+            ));
+        }
+        let mut syndrome = HqcGf2::zero_with_len(2 * n);
+        for i in u.ones_indices() {
+            syndrome.set(i);
+        }
+        for i in v.ones_indices() {
+            syndrome.set(n + i);
+        }
+        Ok(Self { h, s, weights, syndrome })
+    }
+}
+
+impl SdInstance for QcTripleCirculant {
+    fn code_len(&self) -> usize {
+        3 * self.h.n
+    }
+
+    fn redundancy(&self) -> usize {
+        2 * self.h.n
+    }
+
+    fn weight(&self) -> usize {
+        self.weights.iter().sum()
+    }
+
+    fn syndrome(&self) -> &HqcGf2 {
+        &self.syndrome
+    }
+
+    fn column_into(&self, c: usize, out: &mut HqcGf2, tmp: &mut Vec<u64>) {
+        let n = self.h.n;
+        out.clear_all();
+        match c / n {
+            0 => out.set(c),
+            1 => {
+                let mut rot = HqcGf2::zero_with_len(n);
+                self.h.rotate_left_into(c - n, &mut rot, tmp);
+                for i in rot.ones_indices() {
+                    out.set(i);
+                }
+                self.s.rotate_left_into(c - n, &mut rot, tmp);
+                for i in rot.ones_indices() {
+                    out.set(n + i);
+                }
+            }
+            _ => out.set(c - n),
+        }
+    }
+
+    fn syndrome_of(&self, e: &HqcGf2) -> HqcGf2 {
+        let n = self.h.n;
+        let r2 = block(e, 1, n);
+        let mut u = block(e, 0, n);
+        u.xor_in_place(&self.h.mul_bitpacked(&r2));
+        let mut v = block(e, 2, n);
+        v.xor_in_place(&self.s.mul_bitpacked(&r2));
+        let mut out = HqcGf2::zero_with_len(2 * n);
+        for i in u.ones_indices() {
+            out.set(i);
+        }
+        for i in v.ones_indices() {
+            out.set(n + i);
+        }
+        out
+    }
+
+    fn weight_profile_ok(&self, e: &HqcGf2) -> bool {
+        block_weights_match(e, self.h.n, &self.weights)
+    }
+}
+
+/// A random binary `[n, k]` code given by an `(n - k) x n` parity-check matrix, with no
+/// structure beyond the total error weight.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RandomCode {
+    /// Transpose of H: row c is column c of H.
+    columns: BinaryMatrix,
+    pub s: HqcGf2,
+    pub w: usize,
+}
+
+impl RandomCode {
+    /// H given row by row, each of length n.
+    pub fn new(parity_check: &BinaryMatrix, s: HqcGf2, w: usize) -> Result<Self, AttackError> {
+        if s.n != parity_check.rows() || parity_check.rows() > parity_check.cols() {
+            return Err(AttackError::InvalidParameter(
+                "parity check must be (n - k) x n with n - k = s.n".to_string(),
+            ));
+        }
+        Ok(Self { columns: parity_check.transpose(), s, w })
+    }
+
+    /// Uniformly random H and a planted error of weight w; returns the instance and the
+    /// planted error.
+    pub fn random(n: usize, k: usize, w: usize, seed: u64) -> Result<(Self, HqcGf2), AttackError> {
+        if k > n || w > n {
+            return Err(AttackError::InvalidParameter(
+                "need k <= n and w <= n".to_string(),
+            ));
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut columns = BinaryMatrix::zero(n, n - k);
+        for c in 0..n {
+            for r in 0..n - k {
+                if rng.r#gen::<bool>() {
+                    columns.set(c, r);
+                }
+            }
+        }
+        let support = sample(&mut rng, n, w).into_vec();
+        let e = HqcGf2::from_indices(n, &support);
+        let mut code = Self {
+            columns,
+            s: HqcGf2::zero_with_len(n - k),
+            w,
+        };
+        code.s = code.syndrome_of(&e);
+        Ok((code, e))
+    }
+
+    /// H, row by row.
+    pub fn parity_check(&self) -> BinaryMatrix {
+        self.columns.transpose()
+    }
+}
+
+impl SdInstance for RandomCode {
+    fn code_len(&self) -> usize {
+        self.columns.rows()
+    }
+
+    fn redundancy(&self) -> usize {
+        self.columns.cols()
+    }
+
+    fn weight(&self) -> usize {
+        self.w
+    }
+
+    fn syndrome(&self) -> &HqcGf2 {
+        &self.s
+    }
+
+    fn column_into(&self, c: usize, out: &mut HqcGf2, _tmp: &mut Vec<u64>) {
+        out.words.copy_from_slice(self.columns.row(c));
+    }
+}
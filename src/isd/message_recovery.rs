@@ -0,0 +1,79 @@
+use super::attack::{Attack, AttackStats};
+use super::budget::AttackBudget;
+use super::error::AttackError;
+use super::instance::QcDoubleCirculant;
+use crate::hqc::hqcgf2::HqcGf2;
+use crate::hqc::params::HqcPkeParams;
+use crate::hqc::pke::{expand_public_key, rmrs_codec};
+use crate::hqc::types::{CiphPke, EkPke};
+use core::marker::PhantomData;
+
+/// HQC message recovery from a public key and one ciphertext.
+///
+/// `u = r1 + h·r2` with `wt(r1) = wt(r2) = W_R` has the same `[h | I]` shape as key recovery,
+/// with r2 in the role of y. Once r2 is known, `v - s·r2 = m·G + e` truncated to `n1·n2` bits,
+/// and `RmrsCode::decode` removes e.
+pub struct HqcMessageRecoveryInstance<P: HqcPkeParams> {
+    h: HqcGf2,
+    s: HqcGf2,
+    u: HqcGf2,
+    v: HqcGf2,
+    _pd: PhantomData<P>,
+}
+
+impl<P: HqcPkeParams> HqcMessageRecoveryInstance<P> {
+    pub fn new(ek: &EkPke<P>, c: &CiphPke<P>) -> Self {
+        let (h, s) = expand_public_key(ek);
+        Self {
+            h,
+            s,
+            u: HqcGf2::from_bytes_le_bits(P::N, &c.u),
+            v: HqcGf2::from_bytes_le_bits(P::N1N2_BITS, &c.v),
+            _pd: PhantomData,
+        }
+    }
+
+    /// Length n and weight `W_R` of the ISD problem.
+    pub fn params(&self) -> (usize, usize) {
+        (P::N, P::W_R)
+    }
+
+    /// `(h, u)`: pass these to `Attack::solve` in place of `(h, s)`.
+    pub fn get_public_key(&self) -> (&HqcGf2, &HqcGf2) {
+        (&self.h, &self.u)
+    }
+
+    /// The ISD problem as an `SdInstance`.
+    pub fn sd_instance(&self) -> QcDoubleCirculant {
+        QcDoubleCirculant {
+            h: self.h.clone(),
+            s: self.u.clone(),
+            w: P::W_R,
+        }
+    }
+
+    /// Decode `v - s·r2`; None if the RMRS decoder fails (e.g. r2 is wrong).
+    pub fn decrypt_with_r2(&self, r2: &HqcGf2) -> Option<Vec<u8>> {
+        let sr2 = self.s.mul_bitpacked(r2).truncate(P::N1N2_BITS);
+        let mut cw = self.v.clone();
+        cw.xor_in_place(&sr2);
+        rmrs_codec::<P>().decode(&cw.to_bytes_le_bits()).ok()
+    }
+
+    /// Find r2 with `attack`, then decode the message. Ok(None) if the attack finds nothing
+    /// or its r2 does not decode.
+    pub fn solve<A: Attack + ?Sized>(&self, attack: &A) -> Result<Option<Vec<u8>>, AttackError> {
+        self.solve_within(attack, &AttackBudget::unlimited(), &mut AttackStats::default())
+    }
+
+    /// `solve` under `budget`, recording the attack's work into `stats`.
+    pub fn solve_within<A: Attack + ?Sized>(
+        &self,
+        attack: &A,
+        budget: &AttackBudget,
+        stats: &mut AttackStats,
+    ) -> Result<Option<Vec<u8>>, AttackError> {
+        let r2 = attack.solve_within(P::N, P::W_R, &self.h, &self.u, budget, stats)?;
+        Ok(r2.and_then(|r2| self.decrypt_with_r2(&r2)))
+    }
+}
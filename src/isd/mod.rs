@@ -1,6 +1,15 @@
 pub mod error;
 pub mod params;
 pub mod attack;
+pub mod budget;
+pub mod checkpoint;
+pub mod runner;
+pub mod verify;
+pub mod instance;
+pub mod message_recovery;
+pub mod experiment;
+pub mod fit;
 pub mod algorithm;
+pub mod estimator;
 pub use error::*;
-pub use algorithm::{brute_force::BruteForce, prange::Prange, stern::Stern};
\ No newline at end of file
+pub use algorithm::{ball_collision::BallCollision, birthday::BirthdaySearch, bjmm::Bjmm, brute_force::BruteForce, doom::Doom, dumer::Dumer, lee_brickell::LeeBrickell, may_ozerov::MayOzerov, mmt::Mmt, nn::NnBackend, prange::Prange, rlpn::Rlpn, sieve::Sieve, stern::Stern};
\ No newline at end of file
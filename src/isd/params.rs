@@ -4,7 +4,9 @@ use crate::hqc::hqcgf2::HqcGf2;
 use crate::hqc::sampling::{sample_fixed_weight_vect, sample_vect};
 use crate::hqc::types::Seed32;
 use super::error::{HqcParamError, HqcKeygenError};
+use super::verify::{Verdict, classify};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct HqcExperimentParams {
     pub n: usize,
     pub w: usize,
@@ -81,6 +83,48 @@ impl HqcExperimentParams {
         Ok(out)
     }
 }
+/// The scaling families `sparse_parameters_hqc_*`, each tied to the full parameter set whose
+/// n/w ratio it keeps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HqcFamily {
+    Hqc1,
+    Hqc3,
+    Hqc5,
+}
+
+impl HqcFamily {
+    pub const ALL: [HqcFamily; 3] = [HqcFamily::Hqc1, HqcFamily::Hqc3, HqcFamily::Hqc5];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HqcFamily::Hqc1 => "hqc-1",
+            HqcFamily::Hqc3 => "hqc-3",
+            HqcFamily::Hqc5 => "hqc-5",
+        }
+    }
+
+    /// The family member of weight w.
+    pub fn params(self, w: usize) -> HqcExperimentParams {
+        match self {
+            HqcFamily::Hqc1 => HqcExperimentParams::sparse_parameters_hqc_1(w),
+            HqcFamily::Hqc3 => HqcExperimentParams::sparse_parameters_hqc_3(w),
+            HqcFamily::Hqc5 => HqcExperimentParams::sparse_parameters_hqc_5(w),
+        }
+    }
+
+    /// The full parameter set the family scales down.
+    pub fn target(self) -> HqcExperimentParams {
+        match self {
+            HqcFamily::Hqc1 => HqcExperimentParams::hqc_1(),
+            HqcFamily::Hqc3 => HqcExperimentParams::hqc_3(),
+            HqcFamily::Hqc5 => HqcExperimentParams::hqc_5(),
+        }
+    }
+
+    pub fn contains(self, n: usize, w: usize) -> bool {
+        self.params(w).n == n
+    }
+}
 pub struct HqcKeyRecoveryInstance {
     y: HqcGf2,
     x: HqcGf2,
@@ -119,4 +163,8 @@ impl HqcKeyRecoveryInstance {
     pub fn get_secret_key(&self) -> (&HqcGf2, &HqcGf2) {
         (&self.y, &self.x)
     }
+    /// Verify a y returned by an attack and compare it with the planted key.
+    pub fn classify(&self, found: &HqcGf2) -> Verdict {
+        classify(self.y.weight() as usize, &self.h, &self.s, found, &self.y)
+    }
 }
\ No newline at end of file
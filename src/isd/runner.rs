@@ -0,0 +1,167 @@
+use super::attack::{Attack, AttackReport, AttackStats};
+use super::budget::{AttackBudget, CancelFlag};
+use super::error::AttackError;
+use super::verify::is_solution;
+use crate::hqc::hqcgf2::HqcGf2;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Runs one randomized attack on several threads.
+///
+/// Worker i builds its attack from the i-th seed derived from `master_seed`, so a run is
+/// reproducible up to which worker finishes first. All workers share a stop flag, which is
+/// set as soon as one of them returns a verified solution or the caller's cancel flag fires.
+/// The caller's iteration and memory caps are split evenly between workers.
+pub struct ParallelRunner {
+    pub threads: usize,
+    pub master_seed: u64,
+}
+
+/// Outcome of `ParallelRunner::run`.
+#[derive(Clone, Debug)]
+pub struct ParallelReport {
+    pub solution: Option<HqcGf2>,
+    /// Index of the worker whose solution was accepted.
+    pub winner: Option<usize>,
+    /// One report per worker, in worker order.
+    pub workers: Vec<AttackReport>,
+    pub wall_time: Duration,
+}
+
+impl ParallelReport {
+    /// Counters summed over workers; `max_table_len` is the largest over workers and `seed`
+    /// is the winner's.
+    pub fn total_stats(&self) -> AttackStats {
+        let mut total = AttackStats::default();
+        for r in &self.workers {
+            let s = &r.stats;
+            total.iterations += s.iterations;
+            total.eliminations += s.eliminations;
+            total.singular_skips += s.singular_skips;
+            total.table_entries += s.table_entries;
+            total.max_table_len = total.max_table_len.max(s.max_table_len);
+            total.candidate_checks += s.candidate_checks;
+        }
+        total.seed = self.winner.and_then(|i| self.workers[i].stats.seed);
+        total
+    }
+}
+
+impl ParallelRunner {
+    pub fn new(threads: usize, master_seed: u64) -> Self {
+        Self { threads, master_seed }
+    }
+
+    /// Seeds handed to the workers, in worker order.
+    pub fn worker_seeds(&self) -> Vec<u64> {
+        let mut rng = StdRng::seed_from_u64(self.master_seed);
+        (0..self.threads).map(|_| rng.next_u64()).collect()
+    }
+
+    /// Run `make_attack(seed)` on every worker until one finds a verified solution or all of
+    /// them give up. Without a verified solution, the error of the lowest-index worker that
+    /// failed is returned instead; with one, workers that failed get an empty report.
+    pub fn run<A, F>(
+        &self,
+        make_attack: F,
+        n: usize,
+        w: usize,
+        h: &HqcGf2,
+        s: &HqcGf2,
+        budget: &AttackBudget,
+    ) -> Result<ParallelReport, AttackError>
+    where
+        A: Attack,
+        F: Fn(u64) -> A + Sync,
+    {
+        if self.threads == 0 {
+            return Err(AttackError::InvalidParameter(
+                "parameter threads must be > 0".to_string(),
+            ));
+        }
+        let start = Instant::now();
+        let stop = CancelFlag::new();
+        if budget.cancel.as_ref().is_some_and(CancelFlag::is_cancelled) {
+            stop.cancel();
+        }
+        let worker_budget = self.worker_budget(budget, &stop);
+        let seeds = self.worker_seeds();
+        let (tx, rx) = mpsc::channel();
+
+        let mut reports: Vec<Option<Result<AttackReport, AttackError>>> =
+            (0..self.threads).map(|_| None).collect();
+        let mut winner = None;
+        thread::scope(|scope| {
+            for (i, &seed) in seeds.iter().enumerate() {
+                let tx = tx.clone();
+                let make_attack = &make_attack;
+                let worker_budget = &worker_budget;
+                scope.spawn(move || {
+                    let attack = make_attack(seed);
+                    let report = attack.report_within(n, w, h, s, worker_budget);
+                    let _ = tx.send((i, report));
+                });
+            }
+            drop(tx);
+
+            let mut pending = self.threads;
+            while pending > 0 {
+                let (i, report) = match rx.recv_timeout(Duration::from_millis(10)) {
+                    Ok(msg) => msg,
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        if budget.cancel.as_ref().is_some_and(CancelFlag::is_cancelled) {
+                            stop.cancel();
+                        }
+                        continue;
+                    }
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                };
+                pending -= 1;
+                match &report {
+                    Ok(r) => {
+                        let verified = r.solution.as_ref().is_some_and(|y| is_solution(w, h, s, y));
+                        if verified && winner.is_none() {
+                            winner = Some(i);
+                            stop.cancel();
+                        }
+                    }
+                    Err(_) => stop.cancel(),
+                }
+                reports[i] = Some(report);
+            }
+        });
+
+        let mut workers = Vec::with_capacity(self.threads);
+        for report in reports {
+            match report.expect("every worker reports") {
+                Ok(report) => workers.push(report),
+                Err(_) if winner.is_some() => workers.push(AttackReport {
+                    solution: None,
+                    stats: AttackStats::default(),
+                    wall_time: Duration::ZERO,
+                }),
+                Err(e) => return Err(e),
+            }
+        }
+        let solution = winner.and_then(|i| workers[i].solution.clone());
+        Ok(ParallelReport {
+            solution,
+            winner,
+            workers,
+            wall_time: start.elapsed(),
+        })
+    }
+
+    fn worker_budget(&self, budget: &AttackBudget, stop: &CancelFlag) -> AttackBudget {
+        let t = self.threads as u64;
+        AttackBudget {
+            deadline: budget.deadline,
+            max_iters: budget.max_iters.map(|m| m.div_ceil(t)),
+            max_memory_bytes: budget.max_memory_bytes.map(|m| m / self.threads),
+            cancel: Some(stop.clone()),
+        }
+    }
+}
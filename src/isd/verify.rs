@@ -0,0 +1,103 @@
+//! One notion of success for key recovery: `wt(x) = wt(y) = w` and `x + h·y = s`.
+
+use super::error::SolutionError;
+use crate::gf::gf2::Gf2Construct;
+use crate::hqc::hqcgf2::HqcGf2;
+
+/// How a returned y relates to the planted key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// y is not a solution.
+    Invalid(SolutionError),
+    /// y is the planted key.
+    Planted,
+    /// y is the planted key rotated: `y = X^shift · y_planted`.
+    Rotation { shift: usize },
+    /// A valid solution unrelated to the planted key.
+    Other,
+}
+
+impl Verdict {
+    pub fn is_valid(&self) -> bool {
+        !matches!(self, Verdict::Invalid(_))
+    }
+}
+
+/// Check y against `(h, s)` and return the matching x.
+pub fn check(w: usize, h: &HqcGf2, s: &HqcGf2, y: &HqcGf2) -> Result<HqcGf2, SolutionError> {
+    check_len("h", s.n, h)?;
+    check_len("y", s.n, y)?;
+    check_weight("y", w, y)?;
+    let mut x = s.clone();
+    x.xor_in_place(&h.mul_bitpacked(y));
+    check_weight("x", w, &x)?;
+    Ok(x)
+}
+
+/// Check a full key pair: lengths, `wt(x) = wt(y) = w` and `x + h·y = s`.
+pub fn check_pair(
+    w: usize,
+    h: &HqcGf2,
+    s: &HqcGf2,
+    x: &HqcGf2,
+    y: &HqcGf2,
+) -> Result<(), SolutionError> {
+    check_len("x", s.n, x)?;
+    check_weight("x", w, x)?;
+    let expected = check(w, h, s, y)?;
+    if *x != expected {
+        return Err(SolutionError::EquationFailed);
+    }
+    Ok(())
+}
+
+pub fn is_solution(w: usize, h: &HqcGf2, s: &HqcGf2, y: &HqcGf2) -> bool {
+    check(w, h, s, y).is_ok()
+}
+
+/// Verify `found` and compare it with the planted y.
+pub fn classify(w: usize, h: &HqcGf2, s: &HqcGf2, found: &HqcGf2, planted: &HqcGf2) -> Verdict {
+    if let Err(e) = check(w, h, s, found) {
+        return Verdict::Invalid(e);
+    }
+    if found == planted {
+        return Verdict::Planted;
+    }
+    match rotation_between(planted, found) {
+        Some(shift) => Verdict::Rotation { shift },
+        None => Verdict::Other,
+    }
+}
+
+/// The shift with `b = X^shift · a`, if there is one.
+///
+/// Only shifts that map the first one of `b` onto a one of `a` are tried.
+pub fn rotation_between(a: &HqcGf2, b: &HqcGf2) -> Option<usize> {
+    let n = a.n;
+    if b.n != n || a.weight() != b.weight() {
+        return None;
+    }
+    let first = *b.ones_indices().first()?;
+    let mut rotated = HqcGf2::zero_with_len(n);
+    let mut tmp = Vec::new();
+    a.ones_indices().into_iter().find_map(|i| {
+        let shift = (first + n - i) % n;
+        a.rotate_left_into(shift, &mut rotated, &mut tmp);
+        (rotated == *b).then_some(shift)
+    })
+}
+
+fn check_len(name: &'static str, expected: usize, v: &HqcGf2) -> Result<(), SolutionError> {
+    if v.n != expected {
+        return Err(SolutionError::WrongLength { name, expected, got: v.n });
+    }
+    Ok(())
+}
+
+fn check_weight(name: &'static str, expected: usize, v: &HqcGf2) -> Result<(), SolutionError> {
+    let got = v.weight() as usize;
+    if got != expected {
+        return Err(SolutionError::WrongWeight { name, expected, got });
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use isd4hqc::gf::BinaryMatrix;
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::isd::algorithm::helper::gaussian_elimination_for_isd_instance;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn random_matrix(rng: &mut StdRng, rows: usize, cols: usize) -> BinaryMatrix {
+    let mut m = BinaryMatrix::zero(rows, cols);
+    for r in 0..rows {
+        for c in 0..cols {
+            if rng.r#gen::<bool>() {
+                m.set(r, c);
+            }
+        }
+    }
+    m
+}
+
+#[test]
+fn transpose_matches_naive() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let m = random_matrix(&mut rng, 70, 150);
+    let t = m.transpose();
+    assert_eq!((t.rows(), t.cols()), (150, 70));
+    for r in 0..70 {
+        for c in 0..150 {
+            assert_eq!(m.get(r, c), t.get(c, r));
+        }
+    }
+    assert_eq!(t.transpose(), m);
+}
+
+#[test]
+fn systematize_matches_isd_elimination() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let n = 100;
+    loop {
+        let m = random_matrix(&mut rng, n, n);
+        let rhs: Vec<usize> = (0..n).filter(|_| rng.r#gen::<bool>()).collect();
+        let rhs = HqcGf2::from_indices(n, &rhs);
+
+        let mut rows: Vec<HqcGf2> = (0..n).map(|r| m.row_vec(r)).collect();
+        let mut expected = rhs.clone();
+        let invertible = gaussian_elimination_for_isd_instance(&mut rows, &mut expected);
+
+        let mut aug = BinaryMatrix::zero(n, n + 1);
+        for r in 0..n {
+            aug.set_row(r, &m.row_vec(r));
+            aug.set_to(r, n, rhs.get(r));
+        }
+        assert_eq!(aug.systematize(n), invertible);
+        if invertible {
+            assert_eq!(aug.column(n), expected);
+            for r in 0..n {
+                assert_eq!(aug.row_bits(r, 0, n), HqcGf2::from_indices(n, &[r]));
+            }
+            return;
+        }
+    }
+}
+
+#[test]
+fn partial_systematize_defers_last_rows() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let (rows, cols, pivots) = (40, 90, 30);
+    let mut m = random_matrix(&mut rng, rows, cols);
+    assert!(m.systematize(pivots));
+    for r in 0..rows {
+        for c in 0..pivots {
+            assert_eq!(m.get(r, c), r == c);
+        }
+    }
+}
+
+#[test]
+fn rank_kernel_and_solve() {
+    let mut rng = StdRng::seed_from_u64(4);
+    let (rows, cols) = (50, 80);
+    let mut m = random_matrix(&mut rng, rows, cols);
+    // make row 49 dependent so rank < rows
+    for c in 0..cols {
+        let v = m.get(0, c) ^ m.get(1, c);
+        m.set_to(49, c, v);
+    }
+    let rank = m.rank();
+    assert_eq!(rank, 49);
+
+    let kernel = m.kernel();
+    assert_eq!(kernel.len(), cols - rank);
+    for x in &kernel {
+        assert!(m.mul_vec(x).ones_indices().is_empty());
+    }
+
+    let x0 = HqcGf2::from_indices(cols, &[1, 5, 64, 79]);
+    let b = m.mul_vec(&x0);
+    let x = m.solve(&b).expect("consistent");
+    assert_eq!(m.mul_vec(&x), b);
+
+    // row 49 = row 0 + row 1, so flipping only b[49] makes the system inconsistent
+    let mut bad = b.clone();
+    bad.toggle(49);
+    assert!(m.solve(&bad).is_none());
+}
+
+#[test]
+fn identity_rank() {
+    assert_eq!(BinaryMatrix::identity(130).rank(), 130);
+    assert_eq!(BinaryMatrix::zero(5, 7).rank(), 0);
+}
@@ -0,0 +1,291 @@
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::budget::{AttackBudget, CancelFlag, StopReason};
+use isd4hqc::isd::params::{HqcExperimentParams, HqcKeyRecoveryInstance};
+use isd4hqc::isd::{BallCollision, Bjmm, BruteForce, Doom, Dumer, LeeBrickell, Mmt, Prange, Stern};
+use std::time::Duration;
+
+fn instance(n: usize, w: usize) -> (HqcExperimentParams, HqcKeyRecoveryInstance) {
+    let params = HqcExperimentParams::new(n, w);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    (params, inst)
+}
+
+fn assert_valid(params: &HqcExperimentParams, inst: &HqcKeyRecoveryInstance, y: &HqcGf2) {
+    let (h, s) = inst.get_public_key();
+    let mut x = s.clone();
+    x.xor_in_place(&h.mul_bitpacked(y));
+    assert_eq!(y.weight() as usize, params.w);
+    assert_eq!(x.weight() as usize, params.w);
+}
+
+#[test]
+fn prange_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Prange::new(Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn stern_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Stern::new(1, 8, Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn stern_rejects_oversized_window() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let res = Stern::new(1, 65, Some(1), Some(1)).solve(params.n, params.w, h, s);
+    assert!(res.is_err());
+}
+
+#[test]
+fn dumer_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Dumer::new(1, 8, Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn dumer_rejects_invalid_parameters() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    assert!(Dumer::new(1, 65, Some(1), Some(1)).solve(params.n, params.w, h, s).is_err());
+    assert!(Dumer::new(4, 8, Some(1), Some(1)).solve(params.n, params.w, h, s).is_err());
+}
+
+#[test]
+fn lee_brickell_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = LeeBrickell::new(2, Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn bjmm_depth2_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Bjmm::new(2, 2, 10, vec![1], Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn bjmm_depth3_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Bjmm::new(3, 4, 12, vec![1, 1], Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn mmt_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Mmt::new(4, 12, Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn ball_collision_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = BallCollision::new(1, 1, 10, Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn doom_prange_recovers_original_rotation() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Doom::prange(Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn doom_stern_recovers_original_rotation() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Doom::stern(1, 8, Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn prange_incremental_recovers_small_instance() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let y = Prange::new(Some(200000), Some(1))
+        .with_step(1)
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert_valid(&params, &inst, &y);
+}
+
+#[test]
+fn prange_report_counts_work_and_seed() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let report = Prange::new(Some(5000), Some(1))
+        .solve_with_report(params.n, params.w, h, s)
+        .expect("no error");
+    assert_valid(&params, &inst, report.solution.as_ref().expect("solution"));
+    let stats = &report.stats;
+    assert_eq!(stats.seed, Some(1));
+    assert!(stats.iterations > 0);
+    assert_eq!(stats.eliminations, stats.iterations);
+    assert_eq!(stats.candidate_checks + stats.singular_skips, stats.eliminations);
+    assert_eq!(stats.max_table_len, 0);
+}
+
+#[test]
+fn stern_report_records_table_sizes() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let report = Stern::new(1, 8, Some(5000), Some(1))
+        .solve_with_report(params.n, params.w, h, s)
+        .expect("no error");
+    assert!(report.solution.is_some());
+    let stats = &report.stats;
+    let solved = stats.eliminations - stats.singular_skips;
+    assert_eq!(stats.max_table_len, params.n / 2);
+    assert_eq!(stats.table_entries, solved * (params.n / 2) as u64);
+    assert!(stats.candidate_checks > 0);
+}
+
+#[test]
+fn report_matches_solve_for_fixed_seed() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let attack = Bjmm::new(2, 2, 10, vec![1], Some(5000), Some(3));
+    let y = attack.solve(params.n, params.w, h, s).expect("no error");
+    let report = attack.solve_with_report(params.n, params.w, h, s).expect("no error");
+    assert_eq!(report.solution, y);
+    assert!(report.stats.table_entries > 0);
+}
+
+#[test]
+fn brute_force_report_has_no_seed() {
+    let (params, inst) = instance(40, 1);
+    let (h, s) = inst.get_public_key();
+    let report = BruteForce::new(None)
+        .solve_with_report(params.n, params.w, h, s)
+        .expect("no error");
+    assert!(report.solution.is_some());
+    assert_eq!(report.stats.seed, None);
+    assert_eq!(report.stats.iterations, report.stats.candidate_checks);
+}
+
+#[test]
+fn cancelled_budget_stops_before_first_iteration() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let cancel = CancelFlag::new();
+    cancel.cancel();
+    let budget = AttackBudget::unlimited().with_cancel(cancel);
+    let report = Stern::new(1, 8, None, Some(1))
+        .report_within(params.n, params.w, h, s, &budget)
+        .expect("no error");
+    assert!(report.solution.is_none());
+    assert_eq!(report.stats.stopped, Some(StopReason::Cancelled));
+    assert_eq!(report.stats.iterations, 0);
+}
+
+#[test]
+fn budget_iteration_cap_overrides_attack_cap() {
+    let (params, inst) = instance(1000, 20);
+    let (h, s) = inst.get_public_key();
+    let budget = AttackBudget::unlimited().with_max_iters(3);
+    let report = Prange::new(None, Some(1))
+        .report_within(params.n, params.w, h, s, &budget)
+        .expect("no error");
+    assert!(report.solution.is_none());
+    assert_eq!(report.stats.iterations, 3);
+    assert_eq!(report.stats.stopped, Some(StopReason::IterationCap));
+}
+
+#[test]
+fn deadline_stops_long_run_with_partial_stats() {
+    let (params, inst) = instance(1000, 20);
+    let (h, s) = inst.get_public_key();
+    let budget = AttackBudget::unlimited().with_time_limit(Duration::from_millis(50));
+    let report = Stern::new(1, 12, None, Some(1))
+        .report_within(params.n, params.w, h, s, &budget)
+        .expect("no error");
+    assert!(report.solution.is_none());
+    assert_eq!(report.stats.stopped, Some(StopReason::Deadline));
+    assert!(report.stats.iterations > 0);
+    assert!(report.stats.table_entries > 0);
+}
+
+#[test]
+fn memory_cap_rejects_oversized_tables() {
+    let (params, inst) = instance(150, 3);
+    let (h, s) = inst.get_public_key();
+    let budget = AttackBudget::unlimited().with_max_memory_bytes(1024);
+    for attack in [
+        Box::new(Stern::new(2, 8, None, Some(1))) as Box<dyn Attack>,
+        Box::new(Bjmm::new(2, 2, 10, vec![1], None, Some(1))),
+        Box::new(Doom::stern(2, 8, None, Some(1))),
+    ] {
+        let report = attack
+            .report_within(params.n, params.w, h, s, &budget)
+            .expect("no error");
+        assert_eq!(report.stats.stopped, Some(StopReason::MemoryCap), "{}", attack.name());
+        assert_eq!(report.stats.iterations, 0);
+    }
+}
+
+#[test]
+fn cancel_from_another_thread() {
+    let (params, inst) = instance(1000, 20);
+    let (h, s) = inst.get_public_key();
+    let cancel = CancelFlag::new();
+    let budget = AttackBudget::unlimited().with_cancel(cancel.clone());
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        cancel.cancel();
+    });
+    let report = Doom::prange(None, Some(1))
+        .report_within(params.n, params.w, h, s, &budget)
+        .expect("no error");
+    canceller.join().expect("canceller");
+    assert!(report.solution.is_none());
+    assert_eq!(report.stats.stopped, Some(StopReason::Cancelled));
+}
+
@@ -0,0 +1,124 @@
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::isd::budget::{AttackBudget, StopReason};
+use isd4hqc::isd::checkpoint::{Checkpoint, Resumable};
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::{BruteForce, Doom, Dumer, MayOzerov, NnBackend, Prange, Rlpn, Sieve, Stern};
+
+fn public_key(n: usize, w: usize) -> (HqcGf2, HqcGf2) {
+    let inst = HqcExperimentParams::new(n, w).keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    (h.clone(), s.clone())
+}
+
+/// Interrupt `attack` after `stop_at` iterations, save and reload the checkpoint, then
+/// finish; the result must match an uninterrupted run.
+fn assert_resume_matches<A: Resumable>(attack: &A, n: usize, w: usize, stop_at: u64, file: &str) {
+    let (h, s) = public_key(n, w);
+    let full = attack.solve_with_report(n, w, &h, &s).expect("no error");
+    assert!(full.stats.iterations > stop_at, "pick a smaller stop_at");
+
+    let mut checkpoint = Checkpoint::new();
+    let budget = AttackBudget::unlimited().with_max_iters(stop_at);
+    let first = attack.resume(n, w, &h, &s, &budget, &mut checkpoint).expect("no error");
+    assert!(first.is_none());
+    assert_eq!(checkpoint.stats.stopped, Some(StopReason::IterationCap));
+    assert_eq!(checkpoint.stats.iterations, stop_at);
+
+    let path = std::env::temp_dir().join(file);
+    checkpoint.save(&path).expect("save");
+    let mut loaded = Checkpoint::load(&path).expect("load");
+    std::fs::remove_file(&path).expect("cleanup");
+    assert_eq!(loaded, checkpoint);
+
+    let rest = attack
+        .resume(n, w, &h, &s, &AttackBudget::unlimited(), &mut loaded)
+        .expect("no error");
+    assert_eq!(rest, full.solution);
+    assert_eq!(loaded.stats.iterations, full.stats.iterations);
+    assert_eq!(loaded.stats.eliminations, full.stats.eliminations);
+    assert_eq!(loaded.stats.seed, full.stats.seed);
+}
+
+#[test]
+fn prange_resume_matches_uninterrupted_run() {
+    assert_resume_matches(&Prange::new(Some(5000), Some(5)), 150, 3, 3, "isd4hqc-prange.ckpt");
+}
+
+#[test]
+fn prange_incremental_resume_matches_uninterrupted_run() {
+    let attack = Prange::new(Some(20000), Some(2)).with_step(1);
+    assert_resume_matches(&attack, 150, 3, 5, "isd4hqc-prange-cc.ckpt");
+}
+
+#[test]
+fn stern_resume_matches_uninterrupted_run() {
+    assert_resume_matches(&Stern::new(1, 8, Some(5000), Some(4)), 150, 3, 1, "isd4hqc-stern.ckpt");
+}
+
+#[test]
+fn dumer_resume_matches_uninterrupted_run() {
+    assert_resume_matches(&Dumer::new(1, 8, Some(5000), Some(4)), 150, 3, 1, "isd4hqc-dumer.ckpt");
+}
+
+#[test]
+fn may_ozerov_resume_matches_uninterrupted_run() {
+    let nn = NnBackend::BitSampling { bits: 16, repetitions: 2 };
+    let attack = MayOzerov::new(1, 4, nn, Some(5000), Some(4));
+    assert_resume_matches(&attack, 150, 3, 1, "isd4hqc-may-ozerov.ckpt");
+}
+
+#[test]
+fn sieve_resume_matches_uninterrupted_run() {
+    let attack = Sieve::new(2, 8, 2, 1 << 18, Some(5000), Some(4));
+    assert_resume_matches(&attack, 150, 3, 1, "isd4hqc-sieve.ckpt");
+}
+
+#[test]
+fn rlpn_resume_matches_uninterrupted_run() {
+    let attack = Rlpn::new(10, 2, 58, 4000, Some(50), Some(1));
+    assert_resume_matches(&attack, 150, 3, 1, "isd4hqc-rlpn.ckpt");
+}
+
+#[test]
+fn doom_resume_matches_uninterrupted_run() {
+    assert_resume_matches(&Doom::prange(Some(5000), Some(3)), 150, 3, 1, "isd4hqc-doom.ckpt");
+}
+
+#[test]
+fn brute_force_resume_matches_uninterrupted_run() {
+    assert_resume_matches(&BruteForce::new(None), 40, 1, 3, "isd4hqc-brute.ckpt");
+}
+
+#[test]
+fn brute_force_length_mismatch_leaves_checkpoint_untouched() {
+    let (h, s) = public_key(40, 1);
+    let mut checkpoint = Checkpoint::new();
+    let short = h.truncate(39);
+    let budget = AttackBudget::unlimited();
+    assert!(BruteForce::new(None).resume(40, 1, &short, &s, &budget, &mut checkpoint).is_err());
+    assert_eq!(checkpoint, Checkpoint::new());
+    let found = BruteForce::new(None).resume(40, 1, &h, &s, &budget, &mut checkpoint).expect("no error");
+    assert!(found.is_some());
+}
+
+#[test]
+fn resume_rejects_checkpoint_of_other_attack() {
+    let (h, s) = public_key(150, 3);
+    let mut checkpoint = Checkpoint::new();
+    let budget = AttackBudget::unlimited().with_max_iters(1);
+    let _ = Stern::new(1, 8, None, Some(1))
+        .resume(150, 3, &h, &s, &budget, &mut checkpoint)
+        .expect("no error");
+    let other = Stern::new(1, 10, None, Some(1)).resume(150, 3, &h, &s, &budget, &mut checkpoint.clone());
+    assert!(other.is_err());
+    let prange = Prange::new(None, Some(1)).resume(150, 3, &h, &s, &budget, &mut checkpoint);
+    assert!(prange.is_err());
+}
+
+#[test]
+fn malformed_checkpoint_is_rejected() {
+    let text = Checkpoint::new().to_text();
+    assert_eq!(Checkpoint::from_text(&text).expect("round trip"), Checkpoint::new());
+    assert!(Checkpoint::from_text("not a checkpoint").is_err());
+    assert!(Checkpoint::from_text(&text.replace("rng_word_pos 0", "rng_word_pos x")).is_err());
+}
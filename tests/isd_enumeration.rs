@@ -0,0 +1,74 @@
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::isd::algorithm::helper::{
+    WindowColumnKeys, for_each_combination_cf, for_each_combination_revolving_cf, h_mul_y_on_window,
+};
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+#[test]
+fn revolving_door_visits_every_combination_once() {
+    for n in 0..=9 {
+        for k in 0..=n + 1 {
+            let mut expected = HashSet::new();
+            let _ = for_each_combination_cf(n, k, |c| {
+                expected.insert(c.to_vec());
+                ControlFlow::Continue(())
+            });
+            let mut seen = HashSet::new();
+            let mut prev: Option<Vec<usize>> = None;
+            let _ = for_each_combination_revolving_cf(n, k, |c, swap| {
+                assert!(c.windows(2).all(|p| p[0] < p[1]) && c.iter().all(|&i| i < n));
+                match (&prev, swap) {
+                    (None, None) => assert_eq!(c, (0..k).collect::<Vec<_>>()),
+                    (Some(p), Some((left, entered))) => {
+                        let mut q: Vec<usize> = p.iter().copied().filter(|&i| i != left).collect();
+                        assert_eq!(q.len() + 1, p.len(), "{} was not in {:?}", left, p);
+                        q.push(entered);
+                        q.sort_unstable();
+                        assert_eq!(q, c);
+                    }
+                    other => panic!("unexpected step {:?}", other),
+                }
+                assert!(seen.insert(c.to_vec()), "{:?} visited twice", c);
+                prev = Some(c.to_vec());
+                ControlFlow::Continue(())
+            });
+            assert_eq!(seen, expected, "n={} k={}", n, k);
+        }
+    }
+}
+
+#[test]
+fn revolving_door_stops_on_break() {
+    let mut calls = 0;
+    let flow = for_each_combination_revolving_cf(8, 3, |_, _| {
+        calls += 1;
+        if calls == 5 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    });
+    assert_eq!(flow, ControlFlow::Break(()));
+    assert_eq!(calls, 5);
+}
+
+#[test]
+fn incremental_window_keys_match_direct_computation() {
+    let n = 67;
+    let h = HqcGf2::from_indices(n, &[0, 3, 10, 22, 40, 41, 66]);
+    let window: Vec<usize> = (0..n).filter(|i| i % 3 != 1).collect();
+    let keys = WindowColumnKeys::new(n, &h, &window);
+    assert_eq!(keys.key_words(), 1);
+    let mut key = vec![0u64; keys.key_words()];
+    let mut direct = vec![0u64; keys.key_words()];
+    let _ = for_each_combination_revolving_cf(20, 3, |support, swap| {
+        let support: Vec<usize> = support.iter().map(|&i| 30 + i).collect();
+        match swap {
+            None => keys.key_of(support.iter().copied(), &mut key),
+            Some((left, entered)) => {
+                keys.toggle(30 + left, &mut key);
+                keys.toggle(30 + entered, &mut key);
+            }
+        }
+        h_mul_y_on_window(n, &h, &support, &window, &mut direct);
+        assert_eq!(key, direct, "{:?}", support);
+        ControlFlow::Continue(())
+    });
+}
@@ -0,0 +1,52 @@
+use isd4hqc::isd::estimator::{self, IsdAlgorithm, SdCostModel, log2_binomial};
+use isd4hqc::isd::params::HqcExperimentParams;
+
+#[test]
+fn log2_binomial_gamma_branch_matches_summation() {
+    let (n, k) = (1000usize, 100usize);
+    let exact: f64 = (0..k).map(|i| ((n - i) as f64 / (i + 1) as f64).log2()).sum();
+    assert!((log2_binomial(n, k) - exact).abs() < 1e-6);
+    assert_eq!(log2_binomial(10, 0), 0.0);
+    assert_eq!(log2_binomial(3, 4), f64::NEG_INFINITY);
+}
+
+#[test]
+fn prange_matches_closed_form() {
+    let (n, w) = (1000usize, 10usize);
+    let est = estimator::estimate(IsdAlgorithm::Prange, n, w);
+    let gauss = 2.0 * (n as f64).log2() + (2.0 * n as f64).log2();
+    let expected = gauss + log2_binomial(2 * n, 2 * w) - log2_binomial(n, 2 * w);
+    assert!((est.time_log2 - expected).abs() < 1e-9);
+}
+
+#[test]
+fn estimates_for_hqc_levels_are_ordered() {
+    let mut prev = 0.0;
+    for params in [
+        HqcExperimentParams::hqc_1(),
+        HqcExperimentParams::hqc_3(),
+        HqcExperimentParams::hqc_5(),
+    ] {
+        let all = estimator::estimate_all(&params);
+        assert_eq!(all.len(), IsdAlgorithm::ALL.len());
+        let time = |a| all.iter().find(|e| e.algorithm == a).unwrap().time_log2;
+        let prange = time(IsdAlgorithm::Prange);
+        assert!(prange > prev);
+        assert!(time(IsdAlgorithm::Stern) < prange);
+        assert!(time(IsdAlgorithm::Dumer) <= time(IsdAlgorithm::Stern) + 1e-9);
+        assert!(time(IsdAlgorithm::Bjmm) < time(IsdAlgorithm::Stern));
+        for e in &all {
+            assert!(e.time_log2.is_finite() && e.memory_log2.is_finite());
+            assert!(e.iterations_log2 <= e.time_log2);
+        }
+        prev = prange;
+    }
+}
+
+#[test]
+fn stern_search_picks_valid_parameters() {
+    let model = SdCostModel::hqc(2000, 20);
+    let est = model.estimate(IsdAlgorithm::Stern);
+    assert!(2 * est.params.p <= model.weight);
+    assert!(est.params.l <= model.code_len - model.dim);
+}
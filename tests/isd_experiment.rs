@@ -0,0 +1,94 @@
+use isd4hqc::isd::experiment::{Experiment, ExperimentGrid, ExperimentRow, RowFormat};
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::{BruteForce, Prange, Stern};
+
+#[test]
+fn grid_seeds_are_deterministic_and_distinct() {
+    let grid = ExperimentGrid::new(3, 5)
+        .with_family(HqcExperimentParams::sparse_parameters_hqc_1, 1..=2)
+        .with_point(60, 2);
+    let runs = grid.runs();
+    assert_eq!(runs.len(), 9);
+    assert_eq!(runs, grid.runs());
+    assert_eq!(runs[0].params, HqcExperimentParams::sparse_parameters_hqc_1(1));
+    assert_eq!(runs[8].params, HqcExperimentParams::new(60, 2));
+    assert_eq!(runs[4], grid.run_at(runs[4].params, 1));
+    for i in 0..runs.len() {
+        for j in i + 1..runs.len() {
+            assert_ne!(runs[i].keygen_seed, runs[j].keygen_seed);
+            assert_ne!(runs[i].attack_seed, runs[j].attack_seed);
+        }
+    }
+    let other = ExperimentGrid::new(3, 6).with_point(60, 2).runs();
+    assert_ne!(other[0].keygen_seed, runs[6].keygen_seed);
+}
+
+#[test]
+fn sweep_produces_one_row_per_attack_and_key() {
+    let grid = ExperimentGrid::new(2, 1).with_point(40, 2).with_point(60, 2);
+    let experiment = Experiment::new(grid)
+        .with_attack("brute", |_| BruteForce::new(None))
+        .with_attack("prange", |seed| Prange::new(Some(5000), Some(seed)));
+    let mut rows = Vec::new();
+    experiment
+        .run(|row| {
+            rows.push(row);
+            Ok(())
+        })
+        .expect("sweep");
+    assert_eq!(rows.len(), 8);
+    for row in &rows {
+        assert!(row.success, "{:?}", row);
+        assert!(row.error.is_none());
+        assert!(row.iterations > 0);
+    }
+    assert!(rows.iter().filter(|r| r.attack == "brute").all(|r| r.planted && r.attack_seed.is_none()));
+    let runs = experiment.grid.runs();
+    for (i, row) in rows.iter().filter(|r| r.attack == "prange").enumerate() {
+        assert_eq!(row.attack_seed, Some(runs[i].attack_seed));
+        assert_eq!((row.n, row.key_index), (runs[i].params.n, runs[i].key_index));
+    }
+}
+
+#[test]
+fn attack_errors_are_recorded_in_the_row() {
+    let grid = ExperimentGrid::new(1, 0).with_point(40, 2);
+    let experiment = Experiment::new(grid).with_attack("stern", |seed| Stern::new(1, 65, Some(10), Some(seed)));
+    let mut rows = Vec::new();
+    experiment
+        .run(|row| {
+            rows.push(row);
+            Ok(())
+        })
+        .expect("sweep");
+    assert_eq!(rows.len(), 1);
+    assert!(!rows[0].success);
+    assert!(rows[0].error.as_deref().is_some_and(|e| e.contains("parameter l")));
+}
+
+#[test]
+fn rows_are_written_as_jsonl_and_csv() {
+    let grid = ExperimentGrid::new(2, 3).with_point(40, 2);
+    let experiment = Experiment::new(grid).with_attack("brute, exhaustive", |_| BruteForce::new(None));
+
+    let mut jsonl = Vec::new();
+    assert_eq!(experiment.run_to(&mut jsonl, RowFormat::Jsonl).expect("jsonl"), 2);
+    let jsonl = String::from_utf8(jsonl).unwrap();
+    let lines: Vec<&str> = jsonl.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("{\"attack\":\"brute, exhaustive\",\"n\":40,\"w\":2,\"key_index\":0,"));
+    assert!(lines[1].contains("\"key_index\":1,"));
+    assert!(lines[0].contains("\"attack_seed\":null,\"success\":true,\"planted\":true,"));
+    assert!(lines[0].ends_with("\"stopped\":null,\"error\":null}"));
+
+    let mut csv = Vec::new();
+    assert_eq!(experiment.run_to(&mut csv, RowFormat::Csv).expect("csv"), 2);
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], ExperimentRow::CSV_HEADER);
+    let columns = ExperimentRow::CSV_HEADER.split(',').count();
+    let row = lines[1].strip_prefix("\"brute, exhaustive\",").expect("quoted label");
+    assert_eq!(row.split(',').count() + 1, columns);
+    assert!(row.starts_with("40,2,0,"));
+}
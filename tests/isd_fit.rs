@@ -0,0 +1,120 @@
+use isd4hqc::isd::FitError;
+use isd4hqc::isd::experiment::ExperimentRow;
+use isd4hqc::isd::fit::{
+    Fit, FitModel, Sample, WorkMeasure, mean_samples, samples, student_t_quantile,
+};
+use isd4hqc::isd::params::{HqcExperimentParams, HqcFamily};
+use std::time::Duration;
+
+fn row(attack: &str, params: HqcExperimentParams, iterations: u64, success: bool) -> ExperimentRow {
+    ExperimentRow {
+        attack: attack.to_string(),
+        n: params.n,
+        w: params.w,
+        key_index: 0,
+        keygen_seed: [0u8; 32],
+        attack_seed: None,
+        success,
+        planted: success,
+        iterations,
+        eliminations: iterations,
+        candidate_checks: 0,
+        max_table_len: 0,
+        wall_time: Duration::from_millis(iterations),
+        stopped: None,
+        error: None,
+    }
+}
+
+#[test]
+fn student_t_quantiles_match_tables() {
+    for (p, dof, expected) in [
+        (0.975, 1, 12.706),
+        (0.975, 10, 2.228),
+        (0.975, 30, 2.042),
+        (0.995, 5, 4.032),
+        (0.9, 3, 1.638),
+    ] {
+        let t = student_t_quantile(p, dof);
+        assert!((t - expected).abs() < 1e-3, "t({}, {}) = {}", p, dof, t);
+        assert!((student_t_quantile(1.0 - p, dof) + t).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn exact_line_is_recovered() {
+    let data: Vec<Sample> = (2..=6)
+        .map(|w| Sample { w, log2_work: 3.0 + 2.5 * w as f64 })
+        .collect();
+    let fit = Fit::new(&data, FitModel::Linear).expect("fit");
+    assert!((fit.coefficients[0] - 3.0).abs() < 1e-9);
+    assert!((fit.slope() - 2.5).abs() < 1e-9);
+    assert!((fit.r_squared - 1.0).abs() < 1e-12);
+    let e = fit.extrapolate_family(HqcFamily::Hqc1, 0.95);
+    assert_eq!(e.params, HqcExperimentParams::hqc_1());
+    assert!((e.log2_work - (3.0 + 2.5 * 66.0)).abs() < 1e-6);
+    assert!(e.upper - e.lower < 1e-6);
+
+    let curved: Vec<Sample> = (2..=8)
+        .map(|w| Sample { w, log2_work: 1.0 + 2.0 * w as f64 + 3.0 * (w as f64).log2() })
+        .collect();
+    let fit = Fit::new(&curved, FitModel::LinearLog).expect("fit");
+    assert!((fit.predict(100) - (201.0 + 3.0 * 100f64.log2())).abs() < 1e-6);
+}
+
+#[test]
+fn intervals_cover_the_fit_and_widen_with_distance() {
+    let noise = [0.3, -0.2, 0.1, -0.4, 0.25, -0.05, 0.15, -0.1];
+    let data: Vec<Sample> = noise
+        .iter()
+        .enumerate()
+        .map(|(i, e)| Sample { w: 2 + i / 2, log2_work: 1.0 + 3.0 * (2 + i / 2) as f64 + e })
+        .collect();
+    let fit = Fit::new(&data, FitModel::Linear).expect("fit");
+    assert_eq!((fit.samples, fit.dof), (8, 6));
+    assert!((fit.slope() - 3.0).abs() < 0.3);
+    let (lo, hi) = fit.confidence_interval(3, 0.95);
+    let (far_lo, far_hi) = fit.confidence_interval(66, 0.95);
+    assert!(lo < fit.predict(3) && fit.predict(3) < hi);
+    assert!(far_hi - far_lo > hi - lo);
+    let (lo99, hi99) = fit.confidence_interval(66, 0.99);
+    assert!(lo99 < far_lo && far_hi < hi99);
+    assert!(far_lo < 1.0 + 3.0 * 66.0 && 1.0 + 3.0 * 66.0 < far_hi);
+}
+
+#[test]
+fn samples_keep_successful_runs_of_the_family() {
+    let f = HqcFamily::Hqc3;
+    let rows = vec![
+        row("prange", f.params(2), 4, true),
+        row("prange", f.params(2), 12, true),
+        row("prange", f.params(3), 32, true),
+        row("prange", f.params(3), 1000, false),
+        row("prange", HqcFamily::Hqc1.params(3), 64, true),
+        row("stern", f.params(3), 8, true),
+    ];
+    let per_run = samples(&rows, "prange", f, WorkMeasure::Iterations);
+    assert_eq!(
+        per_run,
+        vec![
+            Sample { w: 2, log2_work: 2.0 },
+            Sample { w: 2, log2_work: 12f64.log2() },
+            Sample { w: 3, log2_work: 5.0 },
+        ]
+    );
+    let means = mean_samples(&rows, "prange", f, WorkMeasure::Iterations);
+    assert_eq!(means, vec![Sample { w: 2, log2_work: 3.0 }, Sample { w: 3, log2_work: 5.0 }]);
+    let times = samples(&rows, "stern", f, WorkMeasure::WallTime);
+    assert!((times[0].log2_work - 0.008f64.log2()).abs() < 1e-12);
+}
+
+#[test]
+fn underdetermined_fits_are_rejected() {
+    let two = [Sample { w: 2, log2_work: 1.0 }, Sample { w: 3, log2_work: 2.0 }];
+    assert!(matches!(
+        Fit::new(&two, FitModel::Linear),
+        Err(FitError::NotEnoughSamples { needed: 2, got: 2 })
+    ));
+    let same_w = [Sample { w: 4, log2_work: 1.0 }; 5];
+    assert!(matches!(Fit::new(&same_w, FitModel::Linear), Err(FitError::Degenerate)));
+}
@@ -0,0 +1,208 @@
+use isd4hqc::gf::binary_matrix::BinaryMatrix;
+use isd4hqc::gf::gf2::Gf2Construct;
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::isd::attack::{Attack, AttackStats};
+use isd4hqc::isd::budget::AttackBudget;
+use isd4hqc::isd::instance::{QcDoubleCirculant, QcTripleCirculant, RandomCode, SdAttack, SdInstance, block};
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::{BruteForce, Dumer, Prange, Stern};
+use rand::rngs::StdRng;
+use rand::seq::index::sample;
+use rand::{Rng, SeedableRng};
+
+fn random_vec(rng: &mut StdRng, n: usize, w: Option<usize>) -> HqcGf2 {
+    match w {
+        Some(w) => HqcGf2::from_indices(n, &sample(rng, n, w).into_vec()),
+        None => HqcGf2::from_indices(n, &(0..n).filter(|_| rng.r#gen::<bool>()).collect::<Vec<_>>()),
+    }
+}
+
+/// The columns of H summed over the support of e agree with `syndrome_of`.
+fn assert_columns_match_syndrome<I: SdInstance>(instance: &I, rng: &mut StdRng) {
+    let r = instance.redundancy();
+    for _ in 0..5 {
+        let e = random_vec(rng, instance.code_len(), None);
+        let mut sum = HqcGf2::zero_with_len(r);
+        let mut col = HqcGf2::zero_with_len(r);
+        let mut tmp = vec![0u64; HqcGf2::word_len(r)];
+        for c in e.ones_indices() {
+            instance.column_into(c, &mut col, &mut tmp);
+            sum.xor_in_place(&col);
+        }
+        assert_eq!(sum, instance.syndrome_of(&e));
+    }
+}
+
+fn triple_instance(n: usize, weights: [usize; 3], seed: u64) -> (QcTripleCirculant, HqcGf2) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let h = random_vec(&mut rng, n, None);
+    let s = random_vec(&mut rng, n, None);
+    let r1 = random_vec(&mut rng, n, Some(weights[0]));
+    let r2 = random_vec(&mut rng, n, Some(weights[1]));
+    let e = random_vec(&mut rng, n, Some(weights[2]));
+    let mut u = r1.clone();
+    u.xor_in_place(&h.mul_bitpacked(&r2));
+    let mut v = e.clone();
+    v.xor_in_place(&s.mul_bitpacked(&r2));
+    let planted: Vec<usize> = r1
+        .ones_indices()
+        .into_iter()
+        .chain(r2.ones_indices().into_iter().map(|i| n + i))
+        .chain(e.ones_indices().into_iter().map(|i| 2 * n + i))
+        .collect();
+    let instance = QcTripleCirculant::new(h, s, &u, &v, weights).expect("instance");
+    (instance, HqcGf2::from_indices(3 * n, &planted))
+}
+
+#[test]
+fn instances_agree_with_their_columns() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let params = HqcExperimentParams::new(70, 3);
+    let inst = params.keygen([3u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let double = QcDoubleCirculant::new(h.clone(), s.clone(), 3).expect("instance");
+    assert_columns_match_syndrome(&double, &mut rng);
+    let (triple, _) = triple_instance(50, [2, 2, 3], 2);
+    assert_columns_match_syndrome(&triple, &mut rng);
+    let (random, _) = RandomCode::random(90, 40, 5, 3).expect("instance");
+    assert_columns_match_syndrome(&random, &mut rng);
+    assert_eq!((random.code_len(), random.redundancy()), (90, 50));
+    assert_eq!(random.parity_check().rows(), 50);
+}
+
+#[test]
+fn planted_errors_are_solutions_with_the_right_profile() {
+    let params = HqcExperimentParams::new(70, 3);
+    let inst = params.keygen([3u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let (y, x) = inst.get_secret_key();
+    let double = QcDoubleCirculant::new(h.clone(), s.clone(), 3).expect("instance");
+    let e = HqcGf2::from_indices(
+        140,
+        &y.ones_indices().into_iter().chain(x.ones_indices().into_iter().map(|i| 70 + i)).collect::<Vec<_>>(),
+    );
+    assert!(double.is_solution(&e));
+    assert_eq!(&double.y_of(&e), y);
+    assert_eq!(&block(&e, 1, 70), x);
+    // Same syndrome and total weight, but (y, x) weights (4, 2).
+    assert!(!double.weight_profile_ok(&HqcGf2::from_indices(140, &[0, 1, 2, 3, 70, 71])));
+
+    let (triple, planted) = triple_instance(50, [2, 2, 3], 4);
+    assert!(triple.is_solution(&planted));
+    assert_eq!(triple.weight(), 7);
+
+    let (random, planted) = RandomCode::random(60, 30, 4, 5).expect("instance");
+    assert!(random.is_solution(&planted));
+    assert!(!random.is_solution(&HqcGf2::zero_with_len(60)));
+}
+
+#[test]
+fn attacks_solve_random_codes() {
+    let (code, _) = RandomCode::random(200, 100, 4, 7).expect("instance");
+    let budget = AttackBudget::unlimited();
+    let e = Prange::new(Some(100000), Some(1))
+        .solve_instance(&code, &budget, &mut AttackStats::default())
+        .expect("no error")
+        .expect("solution");
+    assert!(code.is_solution(&e));
+    let e = Stern::new(1, 8, Some(100000), Some(2))
+        .solve_instance(&code, &budget, &mut AttackStats::default())
+        .expect("no error")
+        .expect("solution");
+    assert!(code.is_solution(&e));
+    let e = Dumer::new(1, 8, Some(100000), Some(2))
+        .solve_instance(&code, &budget, &mut AttackStats::default())
+        .expect("no error")
+        .expect("solution");
+    assert!(code.is_solution(&e));
+    let e = Prange::new(Some(100000), Some(3))
+        .with_step(1)
+        .solve_instance(&code, &budget, &mut AttackStats::default())
+        .expect("no error")
+        .expect("solution");
+    assert!(code.is_solution(&e));
+
+    let (small, _) = RandomCode::random(30, 15, 3, 8).expect("instance");
+    let mut stats = AttackStats::default();
+    let e = BruteForce::new(None)
+        .solve_instance(&small, &budget, &mut stats)
+        .expect("no error")
+        .expect("solution");
+    assert!(small.is_solution(&e));
+    assert_eq!(stats.iterations, stats.candidate_checks);
+}
+
+#[test]
+fn attacks_solve_triple_circulant_instances() {
+    let (triple, planted) = triple_instance(60, [2, 2, 2], 9);
+    let budget = AttackBudget::unlimited();
+    let e = Prange::new(Some(100000), Some(1))
+        .solve_instance(&triple, &budget, &mut AttackStats::default())
+        .expect("no error")
+        .expect("solution");
+    assert!(triple.is_solution(&e));
+    assert_eq!(e, planted);
+    let e = Stern::new(1, 10, Some(100000), Some(2))
+        .solve_instance(&triple, &budget, &mut AttackStats::default())
+        .expect("no error")
+        .expect("solution");
+    assert_eq!(e, planted);
+}
+
+#[test]
+fn key_recovery_matches_double_circulant_instance() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let double = QcDoubleCirculant::new(h.clone(), s.clone(), params.w).expect("instance");
+    let stern = Stern::new(1, 8, Some(20000), Some(11));
+    let mut generic_stats = AttackStats::default();
+    let e = stern
+        .solve_instance(&double, &AttackBudget::unlimited(), &mut generic_stats)
+        .expect("no error")
+        .expect("solution");
+    let report = stern.solve_with_report(params.n, params.w, h, s).expect("no error");
+    assert_eq!(report.solution, Some(double.y_of(&e)));
+    assert_eq!(report.stats, generic_stats);
+}
+
+#[test]
+fn generic_parameters_are_checked_against_the_instance() {
+    let (code, _) = RandomCode::random(40, 20, 2, 1).expect("instance");
+    let err = Stern::new(2, 8, Some(10), Some(1))
+        .solve_instance(&code, &AttackBudget::unlimited(), &mut AttackStats::default());
+    assert!(err.is_err());
+    let err = Stern::new(1, 21, Some(10), Some(1))
+        .solve_instance(&code, &AttackBudget::unlimited(), &mut AttackStats::default());
+    assert!(err.is_err());
+    assert!(RandomCode::random(10, 11, 2, 1).is_err());
+}
+
+#[test]
+fn incremental_prange_gives_up_on_degenerate_codes() {
+    let budget = AttackBudget::unlimited();
+    let s = HqcGf2::from_indices(4, &[0, 1]);
+
+    // Rank-deficient H: no elimination ever succeeds, each failure counts as an iteration.
+    let zero = RandomCode::new(&BinaryMatrix::zero(4, 10), s.clone(), 3).expect("instance");
+    let mut stats = AttackStats::default();
+    let found = Prange::new(Some(10), Some(1))
+        .with_step(1)
+        .solve_instance(&zero, &budget, &mut stats)
+        .expect("no error");
+    assert!(found.is_none());
+    assert_eq!(stats.iterations, 10);
+    assert_eq!(stats.singular_skips, 10);
+
+    // H = [I | 0]: eliminations succeed but no pivot can move to a zero column.
+    let rows: Vec<HqcGf2> = (0..4).map(|r| HqcGf2::from_indices(6, &[r])).collect();
+    let padded = RandomCode::new(&BinaryMatrix::from_rows(&rows), s, 3).expect("instance");
+    let mut stats = AttackStats::default();
+    let found = Prange::new(Some(200), Some(1))
+        .with_step(1)
+        .solve_instance(&padded, &budget, &mut stats)
+        .expect("no error");
+    assert!(found.is_none());
+    assert_eq!(stats.iterations, 200);
+    assert!(stats.singular_skips < stats.eliminations);
+}
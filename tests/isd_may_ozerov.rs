@@ -0,0 +1,97 @@
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::isd::algorithm::nn::distance;
+use isd4hqc::isd::attack::{Attack, AttackStats};
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::{MayOzerov, NnBackend};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::ops::ControlFlow;
+
+fn close_pairs(nn: NnBackend, left: &[HqcGf2], right: &[HqcGf2], dist: usize) -> (Vec<(usize, usize)>, u64) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut stats = AttackStats::default();
+    let mut pairs = Vec::new();
+    let _ = nn.for_each_close_pair(left, right, dist, &mut rng, &mut stats, |i, j| {
+        pairs.push((i, j));
+        ControlFlow::Continue(())
+    });
+    pairs.sort_unstable();
+    pairs.dedup();
+    (pairs, stats.candidate_checks)
+}
+
+#[test]
+fn backends_only_report_pairs_at_the_target_distance() {
+    // Dense random vectors, like the pivot parts of a reduced parity check.
+    let dim = 200;
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut random = || {
+        let support: Vec<usize> = (0..dim).filter(|_| rng.r#gen::<bool>()).collect();
+        HqcGf2::from_indices(dim, &support)
+    };
+    let left: Vec<HqcGf2> = (0..40).map(|_| random()).collect();
+    let mut right: Vec<HqcGf2> = (0..40).map(|_| random()).collect();
+    // A planted pair at distance 1 from left[7].
+    let mut near = left[7].clone();
+    near.toggle(3);
+    right.push(near);
+
+    let (all, exhaustive_checks) = close_pairs(NnBackend::Exhaustive, &left, &right, 1);
+    assert_eq!(all, vec![(7, 40)]);
+    assert_eq!(exhaustive_checks, (left.len() * right.len()) as u64);
+
+    let nn = NnBackend::BitSampling { bits: 12, repetitions: 8 };
+    let (found, checks) = close_pairs(nn, &left, &right, 1);
+    assert!(found.iter().all(|&(i, j)| distance(&left[i], &right[j]) == 1));
+    assert!(found.contains(&(7, 40)));
+    assert!(checks < exhaustive_checks);
+}
+
+#[test]
+fn may_ozerov_recovers_small_instance() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    for nn in [NnBackend::Exhaustive, NnBackend::BitSampling { bits: 16, repetitions: 4 }] {
+        for l in [0, 6] {
+            let y = MayOzerov::new(1, l, nn, Some(5000), Some(1))
+                .solve(params.n, params.w, h, s)
+                .expect("no error")
+                .expect("solution");
+            assert!(inst.classify(&y).is_valid());
+        }
+    }
+}
+
+#[test]
+fn bit_sampling_compares_fewer_pairs_than_exhaustive() {
+    // Large enough that no run succeeds within the iteration cap.
+    let params = HqcExperimentParams::new(300, 6);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    // Distance computations per successful elimination.
+    let checks_per_iteration = |nn| {
+        let st = MayOzerov::new(1, 0, nn, Some(5), Some(3))
+            .solve_with_report(params.n, params.w, h, s)
+            .expect("no error")
+            .stats;
+        st.candidate_checks / (st.eliminations - st.singular_skips)
+    };
+    let exhaustive = checks_per_iteration(NnBackend::Exhaustive);
+    assert_eq!(exhaustive, 150 * 150);
+    assert!(checks_per_iteration(NnBackend::BitSampling { bits: 16, repetitions: 4 }) < exhaustive / 10);
+}
+
+#[test]
+fn may_ozerov_rejects_invalid_backend() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let bad = [
+        NnBackend::BitSampling { bits: 65, repetitions: 1 },
+        NnBackend::BitSampling { bits: 8, repetitions: 0 },
+    ];
+    for nn in bad {
+        assert!(MayOzerov::new(1, 0, nn, Some(1), Some(1)).solve(params.n, params.w, h, s).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::hqc::params::HqcPkeParams;
+use isd4hqc::hqc::pke;
+use isd4hqc::hqc::types::{CiphPke, EkPke};
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::instance::SdInstance;
+use isd4hqc::isd::message_recovery::HqcMessageRecoveryInstance;
+use isd4hqc::isd::{BruteForce, Prange};
+
+/// HQC-shaped parameters small enough to break: a [3, 1] RS code with generator
+/// (x - α)(x - α²) over HQC's RM(1, 7) x3, and W_R = 1.
+#[derive(Clone, Copy, Debug)]
+struct ToyParams;
+impl HqcPkeParams for ToyParams {
+    const N1: usize = 3;
+    const N2: usize = 384;
+    const N: usize = 1153;
+    const K: usize = 8;
+    const W: usize = 2;
+    const W_R: usize = 1;
+    const W_E: usize = 1;
+    const RM_MULT: usize = 3;
+    const RS_GEN_POLY: &'static [u8] = &[1, 6, 8];
+}
+
+fn toy_ciphertext(m: u8) -> (EkPke<ToyParams>, CiphPke<ToyParams>) {
+    let (ek, dk) = pke::keygen::<ToyParams>([5u8; 32]);
+    let c = pke::encrypt::<ToyParams>(&ek, &[m], [9u8; 32]).expect("encrypt");
+    assert_eq!(pke::decrypt::<ToyParams>(&dk, &c), Some(vec![m]));
+    (ek, c)
+}
+
+#[test]
+fn brute_force_recovers_toy_message() {
+    let (ek, c) = toy_ciphertext(0xa5);
+    let inst = HqcMessageRecoveryInstance::new(&ek, &c);
+    assert_eq!(inst.params(), (ToyParams::N, ToyParams::W_R));
+    assert_eq!(inst.solve(&BruteForce::new(None)).expect("no error"), Some(vec![0xa5]));
+}
+
+#[test]
+fn recovered_r2_solves_the_sd_instance() {
+    let (ek, c) = toy_ciphertext(0x3c);
+    let inst = HqcMessageRecoveryInstance::new(&ek, &c);
+    let (h, u) = inst.get_public_key();
+    let (n, w) = inst.params();
+    let r2 = Prange::new(Some(10000), Some(1)).solve(n, w, h, u).expect("no error").expect("r2");
+    let mut r1 = u.clone();
+    r1.xor_in_place(&h.mul_bitpacked(&r2));
+    let e = HqcGf2::from_indices(
+        2 * n,
+        &r2.ones_indices().into_iter().chain(r1.ones_indices().into_iter().map(|i| n + i)).collect::<Vec<_>>(),
+    );
+    assert!(inst.sd_instance().is_solution(&e));
+    assert_eq!(inst.decrypt_with_r2(&r2), Some(vec![0x3c]));
+}
+
+#[test]
+fn wrong_r2_does_not_decrypt_to_the_message() {
+    let (ek, c) = toy_ciphertext(0x11);
+    let inst = HqcMessageRecoveryInstance::new(&ek, &c);
+    let (h, s) = pke::expand_public_key(&ek);
+    assert_eq!(inst.get_public_key().0, &h);
+    // r2 = s is far from the weight-1 r2 used for encryption.
+    assert_ne!(inst.decrypt_with_r2(&s), Some(vec![0x11]));
+}
@@ -0,0 +1,73 @@
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::hqc::kem::{self, DkKemFormat};
+use isd4hqc::hqc::params::{Hqc1Params, HqcPkeParams};
+use isd4hqc::hqc::pke;
+use isd4hqc::hqc::types::EkPke;
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::instance::{QcDoubleCirculant, SdInstance};
+use isd4hqc::isd::BruteForce;
+
+/// HQC-shaped parameters small enough to break: a [3, 1] RS code with generator
+/// (x - α)(x - α²) over HQC's RM(1, 7) x3, and W = 1.
+#[derive(Clone, Copy, Debug)]
+struct ToyParams;
+impl HqcPkeParams for ToyParams {
+    const N1: usize = 3;
+    const N2: usize = 384;
+    const N: usize = 1153;
+    const K: usize = 8;
+    const W: usize = 1;
+    const W_R: usize = 2;
+    const W_E: usize = 2;
+    const RM_MULT: usize = 3;
+    const RS_GEN_POLY: &'static [u8] = &[1, 6, 8];
+}
+
+/// Break a public key given only as bytes.
+fn recover_y(ek_bytes: &[u8]) -> HqcGf2 {
+    let ek = EkPke::<ToyParams>::from_bytes(ek_bytes).expect("ek bytes");
+    let instance = QcDoubleCirculant::from(&ek);
+    assert_eq!((instance.h.n, instance.w), (ToyParams::N, ToyParams::W));
+    BruteForce::new(None)
+        .solve(instance.h.n, instance.w, &instance.h, &instance.s)
+        .expect("no error")
+        .expect("y")
+}
+
+#[test]
+fn public_key_instance_matches_keygen() {
+    let (ek, _) = pke::keygen::<Hqc1Params>([1u8; 32]);
+    let instance = QcDoubleCirculant::from(&ek);
+    let (h, s) = pke::expand_public_key(&ek);
+    assert_eq!((instance.h, instance.s, instance.w), (h, s, Hqc1Params::W));
+}
+
+#[test]
+fn recovered_y_decrypts_pke_ciphertext() {
+    let (ek, _) = pke::keygen::<ToyParams>([2u8; 32]);
+    let y = recover_y(&ek.to_bytes());
+    let instance = QcDoubleCirculant::from(&ek);
+    let mut e = y.ones_indices();
+    let mut x = instance.s.clone();
+    x.xor_in_place(&instance.h.mul_bitpacked(&y));
+    e.extend(x.ones_indices().into_iter().map(|i| ToyParams::N + i));
+    assert!(instance.is_solution(&HqcGf2::from_indices(2 * ToyParams::N, &e)));
+
+    for m in [0x00u8, 0x5a, 0xff] {
+        let c = pke::encrypt::<ToyParams>(&ek, &[m], [m; 32]).expect("encrypt");
+        assert_eq!(pke::decrypt_with_secret_vector::<ToyParams>(&y, &c), Some(vec![m]));
+    }
+}
+
+#[test]
+fn recovered_y_decapsulates_kem_ciphertext() {
+    let (ek, dk) = kem::keygen_from_seed::<ToyParams>([3u8; 32], DkKemFormat::Compressed).expect("keygen");
+    let y = recover_y(&ek.to_bytes());
+    let (key, c) = kem::encaps_with::<ToyParams>(&ek, &[0x77], [4u8; 16]).expect("encaps");
+    assert_eq!(kem::decaps::<ToyParams>(&dk, &c), key);
+    assert_eq!(kem::decaps_with_secret_vector::<ToyParams>(&ek, &y, &c), Some(key));
+
+    let mut tampered = c.clone();
+    tampered.salt[0] ^= 1;
+    assert_eq!(kem::decaps_with_secret_vector::<ToyParams>(&ek, &y, &tampered), None);
+}
@@ -0,0 +1,57 @@
+use isd4hqc::hqc::params::{Hqc1Params, Hqc3Params, Hqc5Params};
+use isd4hqc::isd::estimator::quantum::{self, QuantumIsdAlgorithm, NIST_MAXDEPTHS_LOG2};
+use isd4hqc::isd::estimator::{self, IsdAlgorithm, SdCostModel};
+use isd4hqc::isd::params::HqcExperimentParams;
+
+#[test]
+fn quantum_prange_is_square_root_of_classical_search() {
+    let model = SdCostModel::hqc(1000, 10);
+    let classical = model.estimate(IsdAlgorithm::Prange);
+    let q = model.quantum_estimate(QuantumIsdAlgorithm::Prange, 1000.0);
+    let pi_4 = (std::f64::consts::PI / 4.0).log2();
+    let expected = pi_4 + classical.iterations_log2 / 2.0 + (classical.time_log2 - classical.iterations_log2);
+    assert!((q.gates_log2 - expected).abs() < 1e-9);
+    // With an unreachable depth limit nothing is parallelised.
+    assert_eq!(q.gates_log2, q.maxdepth_gates_log2);
+}
+
+#[test]
+fn maxdepth_only_increases_gate_count() {
+    let params = HqcExperimentParams::hqc_1();
+    let mut prev = f64::INFINITY;
+    for &d in NIST_MAXDEPTHS_LOG2.iter() {
+        for est in quantum::estimate_all(&params, d) {
+            assert!(est.maxdepth_gates_log2 >= est.gates_log2);
+            if est.algorithm == QuantumIsdAlgorithm::Prange {
+                assert!(est.maxdepth_gates_log2 <= prev);
+                prev = est.maxdepth_gates_log2;
+            }
+        }
+    }
+}
+
+#[test]
+fn hqc_levels_meet_claimed_categories() {
+    for &d in NIST_MAXDEPTHS_LOG2.iter() {
+        let levels = [
+            (1, quantum::estimate_key_recovery::<Hqc1Params>(d)),
+            (3, quantum::estimate_key_recovery::<Hqc3Params>(d)),
+            (5, quantum::estimate_key_recovery::<Hqc5Params>(d)),
+        ];
+        for (category, estimates) in levels {
+            for est in estimates {
+                assert!(est.nist_category() >= category, "{:?}", est);
+            }
+        }
+    }
+    assert_eq!(estimator::nist_category(150.0), 1);
+    assert_eq!(quantum::nist_category(100.0, 40.0), 0);
+}
+
+#[test]
+fn stern_is_no_worse_than_prange() {
+    for est in quantum::estimate_message_recovery::<Hqc1Params>(64.0).chunks(2) {
+        assert_eq!(est[0].algorithm, QuantumIsdAlgorithm::Prange);
+        assert!(est[1].maxdepth_gates_log2 <= est[0].maxdepth_gates_log2);
+    }
+}
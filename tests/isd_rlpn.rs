@@ -0,0 +1,119 @@
+use isd4hqc::gf::hadamard::hadamard_transform;
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::isd::algorithm::helper::SemiSystematicForm;
+use isd4hqc::isd::algorithm::rlpn::{ParityCheck, collect_parity_checks, decode_window, rotations};
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::instance::QcDoubleCirculant;
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::Rlpn;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::ControlFlow;
+
+fn inner(support: &[usize], e: &HqcGf2) -> bool {
+    support.iter().fold(false, |b, &c| b ^ e.get(c))
+}
+
+#[test]
+fn hadamard_transform_matches_definition() {
+    let v: Vec<i32> = vec![3, -1, 0, 2, 5, -4, 1, 1];
+    let mut fast = v.clone();
+    hadamard_transform(&mut fast);
+    for (x, &got) in fast.iter().enumerate() {
+        let want: i32 = v
+            .iter()
+            .enumerate()
+            .map(|(z, &vz)| if (x & z).count_ones() % 2 == 0 { vz } else { -vz })
+            .sum();
+        assert_eq!(got, want);
+    }
+}
+
+#[test]
+fn collected_checks_and_rotations_are_dual_codewords() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let (y, x) = inst.get_secret_key();
+    let e = HqcGf2::from_indices(
+        2 * params.n,
+        &y.ones_indices().into_iter().chain(x.ones_indices().into_iter().map(|i| params.n + i)).collect::<Vec<_>>(),
+    );
+    let qc = QcDoubleCirculant::new(h.clone(), s.clone(), params.w).expect("instance");
+    let mut form = SemiSystematicForm::new(params.n, 0);
+    let mut rng = StdRng::seed_from_u64(1);
+    while !form.reduce(&mut rng, h, s) {}
+    let mut checks = Vec::new();
+    collect_parity_checks(&form, 2, 60, |c| {
+        checks.push(c);
+        if checks.len() < 20 { ControlFlow::Continue(()) } else { ControlFlow::Break(()) }
+    });
+    assert!(!checks.is_empty());
+    for check in &checks {
+        assert!(check.support.len() <= 60);
+        // <c, e> = <u, s> for the planted e.
+        assert_eq!(check.parity, inner(&check.support, &e));
+        for rotated in rotations(check, &qc).take(10) {
+            assert_eq!(rotated.parity, inner(&rotated.support, &e));
+        }
+    }
+}
+
+#[test]
+fn decode_window_finds_biased_pattern() {
+    // Checks dense on 0..8 and of weight 2 on 8..64, with the parity flipped with
+    // probability 0.2: <c_Q, e_Q> is biased towards 0 and so is the noise.
+    let mut rng = StdRng::seed_from_u64(2);
+    let e = HqcGf2::from_indices(64, &[3, 5, 40]);
+    let pool: Vec<ParityCheck> = (0..2000)
+        .map(|_| {
+            let mut support: Vec<usize> = (0..8).filter(|_| rng.r#gen::<bool>()).collect();
+            let (a, b) = (rng.gen_range(8..36), rng.gen_range(36..64));
+            support.extend([a, b]);
+            let parity = inner(&support, &e) ^ rng.gen_bool(0.2);
+            ParityCheck { support, parity }
+        })
+        .collect();
+    assert_eq!(decode_window(&pool, 0, 8), vec![3, 5]);
+}
+
+#[test]
+fn rlpn_recovers_small_instance() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([0u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let y = Rlpn::new(10, 2, 58, 4000, Some(50), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert!(inst.classify(&y).is_valid());
+}
+
+#[test]
+fn rlpn_pool_stays_within_checks() {
+    // 100 checks on n = 150: the rotations of a single check would overshoot the pool.
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([0u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let report = Rlpn::new(10, 2, 58, 100, Some(3), Some(1))
+        .solve_with_report(params.n, params.w, h, s)
+        .expect("no error");
+    assert_eq!(report.stats.max_table_len, 100);
+}
+
+#[test]
+fn rlpn_rejects_invalid_parameters() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let bad = [
+        Rlpn::new(0, 2, 58, 100, Some(1), Some(1)),
+        Rlpn::new(25, 2, 58, 100, Some(1), Some(1)),
+        Rlpn::new(10, 0, 58, 100, Some(1), Some(1)),
+        Rlpn::new(10, 2, 1, 100, Some(1), Some(1)),
+        Rlpn::new(10, 2, 58, 0, Some(1), Some(1)),
+    ];
+    for attack in bad {
+        assert!(attack.solve(params.n, params.w, h, s).is_err());
+    }
+}
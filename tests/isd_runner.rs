@@ -0,0 +1,118 @@
+use isd4hqc::isd::budget::{AttackBudget, StopReason};
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::runner::ParallelRunner;
+use isd4hqc::isd::{Prange, Stern};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn worker_seeds_are_deterministic_and_distinct() {
+    let a = ParallelRunner::new(4, 9).worker_seeds();
+    assert_eq!(a, ParallelRunner::new(4, 9).worker_seeds());
+    assert_ne!(a, ParallelRunner::new(4, 10).worker_seeds());
+    for i in 0..a.len() {
+        for j in i + 1..a.len() {
+            assert_ne!(a[i], a[j]);
+        }
+    }
+}
+
+#[test]
+fn parallel_stern_recovers_small_instance() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let runner = ParallelRunner::new(4, 1);
+    let report = runner
+        .run(
+            |seed| Stern::new(1, 8, Some(5000), Some(seed)),
+            params.n,
+            params.w,
+            h,
+            s,
+            &AttackBudget::unlimited(),
+        )
+        .expect("no error");
+    let y = report.solution.as_ref().expect("solution");
+    let mut x = s.clone();
+    x.xor_in_place(&h.mul_bitpacked(y));
+    assert_eq!(y.weight() as usize, params.w);
+    assert_eq!(x.weight() as usize, params.w);
+    let winner = report.winner.expect("winner");
+    let seeds = runner.worker_seeds();
+    assert_eq!(report.workers.len(), 4);
+    assert_eq!(report.workers[winner].stats.seed, Some(seeds[winner]));
+    assert_eq!(report.total_stats().seed, Some(seeds[winner]));
+}
+
+#[test]
+fn parallel_iteration_cap_is_split_between_workers() {
+    let params = HqcExperimentParams::new(1000, 20);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let budget = AttackBudget::unlimited().with_max_iters(8);
+    let report = ParallelRunner::new(4, 1)
+        .run(|seed| Prange::new(None, Some(seed)), params.n, params.w, h, s, &budget)
+        .expect("no error");
+    assert!(report.solution.is_none());
+    assert_eq!(report.total_stats().iterations, 8);
+    for worker in &report.workers {
+        assert_eq!(worker.stats.stopped, Some(StopReason::IterationCap));
+    }
+}
+
+#[test]
+fn zero_threads_is_rejected() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let res = ParallelRunner::new(0, 1).run(
+        |seed| Prange::new(Some(1), Some(seed)),
+        params.n,
+        params.w,
+        h,
+        s,
+        &AttackBudget::unlimited(),
+    );
+    assert!(res.is_err());
+}
+
+#[test]
+fn late_worker_error_keeps_verified_solution() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let runner = ParallelRunner::new(2, 1);
+    let failing = runner.worker_seeds()[0];
+    // Worker 0 is built late and with an invalid window, so it fails after worker 1 won.
+    let report = runner
+        .run(
+            |seed| {
+                if seed == failing {
+                    thread::sleep(Duration::from_millis(500));
+                    Stern::new(1, 65, Some(5000), Some(seed))
+                } else {
+                    Stern::new(1, 8, Some(5000), Some(seed))
+                }
+            },
+            params.n,
+            params.w,
+            h,
+            s,
+            &AttackBudget::unlimited(),
+        )
+        .expect("winner kept");
+    assert_eq!(report.winner, Some(1));
+    assert!(inst.classify(report.solution.as_ref().expect("solution")).is_valid());
+    assert!(report.workers[0].solution.is_none());
+
+    let res = runner.run(
+        |seed| Stern::new(1, 65, Some(5000), Some(seed)),
+        params.n,
+        params.w,
+        h,
+        s,
+        &AttackBudget::unlimited(),
+    );
+    assert!(res.is_err());
+}
@@ -0,0 +1,76 @@
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::budget::{AttackBudget, StopReason};
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::Sieve;
+
+#[test]
+fn sieve_recovers_small_instance() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let y = Sieve::new(2, 8, 2, 1 << 20, Some(5000), Some(1))
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert!(inst.classify(&y).is_valid());
+}
+
+#[test]
+fn level_sizes_respect_the_list_cap() {
+    let params = HqcExperimentParams::new(300, 6);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let sieve = Sieve::new(2, 12, 3, 1 << 18, Some(5), Some(2));
+    let budget = AttackBudget::unlimited();
+    let (report, levels) = sieve
+        .report_levels_within(params.n, params.w, h, s, &budget)
+        .expect("no error");
+    assert!(report.solution.is_none());
+    let reduced = report.stats.eliminations - report.stats.singular_skips;
+    assert_eq!(levels.len(), 4);
+    assert_eq!(levels[0].samples, reduced);
+    // C(312, 2) weight-2 vectors are far more than fit: level 0 is always full.
+    assert_eq!(levels[0].min, sieve.list_len());
+    for level in &levels {
+        assert!(level.max <= sieve.list_len());
+        assert!(level.min <= level.max);
+    }
+    assert_eq!(report.stats.max_table_len, sieve.list_len());
+}
+
+#[test]
+fn sieve_stops_when_lists_exceed_memory_budget() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let budget = AttackBudget::unlimited().with_max_memory_bytes(1 << 16);
+    let report = Sieve::new(2, 8, 2, 1 << 20, Some(5000), Some(1))
+        .report_within(params.n, params.w, h, s, &budget)
+        .expect("no error");
+    assert!(report.solution.is_none());
+    assert_eq!(report.stats.stopped, Some(StopReason::MemoryCap));
+
+    // memory_bytes covers every list the sieve keeps, including the dedup set.
+    let budget = AttackBudget::unlimited().with_max_memory_bytes(1 << 16);
+    let report = Sieve::new(2, 8, 2, 1 << 16, Some(5), Some(1))
+        .report_within(params.n, params.w, h, s, &budget)
+        .expect("no error");
+    assert_ne!(report.stats.stopped, Some(StopReason::MemoryCap));
+}
+
+#[test]
+fn sieve_rejects_invalid_parameters() {
+    let params = HqcExperimentParams::new(150, 3);
+    let inst = params.keygen([7u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let bad = [
+        Sieve::new(2, 8, 0, 1 << 20, Some(1), Some(1)),
+        Sieve::new(2, 8, 9, 1 << 20, Some(1), Some(1)),
+        Sieve::new(2, 65, 2, 1 << 20, Some(1), Some(1)),
+        Sieve::new(7, 8, 2, 1 << 20, Some(1), Some(1)),
+        Sieve::new(2, 8, 2, 16, Some(1), Some(1)),
+    ];
+    for sieve in bad {
+        assert!(sieve.solve(params.n, params.w, h, s).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use isd4hqc::hqc::hqcgf2::HqcGf2;
+use isd4hqc::isd::attack::Attack;
+use isd4hqc::isd::error::SolutionError;
+use isd4hqc::isd::params::HqcExperimentParams;
+use isd4hqc::isd::verify::{Verdict, check, check_pair, classify, rotation_between};
+use isd4hqc::isd::BruteForce;
+
+fn rotate(v: &HqcGf2, shift: usize) -> HqcGf2 {
+    let idx: Vec<usize> = v.ones_indices().iter().map(|&i| (i + shift) % v.n).collect();
+    HqcGf2::from_indices(v.n, &idx)
+}
+
+#[test]
+fn planted_key_is_planted() {
+    let params = HqcExperimentParams::new(60, 3);
+    let inst = params.keygen([3u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let (y, x) = inst.get_secret_key();
+    assert_eq!(inst.classify(y), Verdict::Planted);
+    assert_eq!(check(params.w, h, s, y).as_ref(), Ok(x));
+    assert_eq!(check_pair(params.w, h, s, x, y), Ok(()));
+}
+
+#[test]
+fn rotated_key_solves_rotated_syndrome() {
+    let params = HqcExperimentParams::new(60, 3);
+    let inst = params.keygen([3u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let (y, _) = inst.get_secret_key();
+    let (s7, y7) = (rotate(s, 7), rotate(y, 7));
+    assert_eq!(rotation_between(y, &y7), Some(7));
+    assert_eq!(classify(params.w, h, &s7, &y7, y), Verdict::Rotation { shift: 7 });
+    // Against the original syndrome the rotated key is not a solution.
+    assert!(!inst.classify(&y7).is_valid());
+}
+
+#[test]
+fn wrong_weights_and_lengths_are_rejected() {
+    let params = HqcExperimentParams::new(60, 3);
+    let inst = params.keygen([3u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let (y, x) = inst.get_secret_key();
+
+    let mut heavy = y.clone();
+    let extra = (0..params.n).find(|&i| !y.get(i)).unwrap();
+    heavy.set(extra);
+    assert_eq!(
+        inst.classify(&heavy),
+        Verdict::Invalid(SolutionError::WrongWeight { name: "y", expected: 3, got: 4 })
+    );
+    assert_eq!(
+        inst.classify(&HqcGf2::from_indices(59, &[0, 1, 2])),
+        Verdict::Invalid(SolutionError::WrongLength { name: "y", expected: 60, got: 59 })
+    );
+
+    let other_x = rotate(x, 1);
+    assert_eq!(check_pair(params.w, h, s, &other_x, y), Err(SolutionError::EquationFailed));
+}
+
+#[test]
+fn other_valid_solution_is_classified_as_other() {
+    // h = 1: any split of supp(s) into two weight-w halves is a key.
+    let n = 20;
+    let h = HqcGf2::from_indices(n, &[0]);
+    let s = HqcGf2::from_indices(n, &[0, 1, 2, 3]);
+    let planted = HqcGf2::from_indices(n, &[0, 1]);
+    let other = HqcGf2::from_indices(n, &[0, 2]);
+    assert_eq!(classify(2, &h, &s, &planted, &planted), Verdict::Planted);
+    assert_eq!(classify(2, &h, &s, &other, &planted), Verdict::Other);
+}
+
+#[test]
+fn brute_force_results_are_verified() {
+    let params = HqcExperimentParams::new(40, 2);
+    let inst = params.keygen([5u8; 32]).expect("keygen");
+    let (h, s) = inst.get_public_key();
+    let y = BruteForce::new(None)
+        .solve(params.n, params.w, h, s)
+        .expect("no error")
+        .expect("solution");
+    assert!(inst.classify(&y).is_valid());
+
+    // A short h is rejected like in every other attack.
+    let short = h.truncate(params.n - 1);
+    assert!(BruteForce::new(None).solve(params.n, params.w, &short, s).is_err());
+}